@@ -0,0 +1,14 @@
+#![no_main]
+
+use aoc2023::day1::{digits, DigitMatcher, ENGLISH, GERMAN, SPANISH};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        let matcher = DigitMatcher::new(&[&ENGLISH, &GERMAN, &SPANISH]);
+        for line in input.lines() {
+            let _ = digits(line);
+            let _ = matcher.first_last(line);
+        }
+    }
+});
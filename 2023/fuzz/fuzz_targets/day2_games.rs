@@ -0,0 +1,12 @@
+#![no_main]
+
+use aoc2023::day2::parse_game;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        for line in input.lines() {
+            let _ = parse_game(line);
+        }
+    }
+});
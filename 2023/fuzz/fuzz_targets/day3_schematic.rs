@@ -0,0 +1,14 @@
+#![no_main]
+
+use aoc2023::day3::Schematic;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        let chars: Vec<Vec<char>> = input.lines().map(|l| l.chars().collect()).collect();
+        if let Ok(schematic) = Schematic::new(&chars) {
+            let _ = schematic.part_numbers().count();
+            let _ = schematic.gears(2).count();
+        }
+    }
+});
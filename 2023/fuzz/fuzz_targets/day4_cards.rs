@@ -0,0 +1,14 @@
+#![no_main]
+
+use aoc2023::day4::parse_card;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        for line in input.lines() {
+            if let Ok((_, card)) = parse_card(line) {
+                let _ = card.matches();
+            }
+        }
+    }
+});
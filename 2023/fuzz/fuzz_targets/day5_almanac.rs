@@ -0,0 +1,13 @@
+#![no_main]
+
+use aoc2023::day5::{parse_input, parse_input_2, part_1};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        if parse_input(input).is_ok() {
+            let _ = part_1(input);
+        }
+        let _ = parse_input_2(input);
+    }
+});
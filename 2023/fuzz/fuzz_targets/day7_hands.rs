@@ -0,0 +1,11 @@
+#![no_main]
+
+use aoc2023::day7::{parse_hands, parse_hands_with_jokers};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        let _ = parse_hands(input);
+        let _ = parse_hands_with_jokers(input);
+    }
+});
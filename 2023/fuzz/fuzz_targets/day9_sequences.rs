@@ -0,0 +1,10 @@
+#![no_main]
+
+use aoc2023::day9::stream_seqs;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        let _ = stream_seqs(input.lines()).count();
+    }
+});
@@ -0,0 +1,103 @@
+use serde::Serialize;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering};
+
+// A global allocator that counts heap use for the runner's allocation stats. It
+// passes everything through to the system allocator and only counts once enabled.
+// The counts cover the whole process, so phases measured at the same time on
+// different threads can't be told apart.
+pub struct Counting;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static ALLOCS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+// Signed, since memory allocated before counting began may be freed after.
+static LIVE: AtomicI64 = AtomicI64::new(0);
+static PEAK: AtomicI64 = AtomicI64::new(0);
+
+fn allocated(size: usize) {
+    if !enabled() {
+        return;
+    }
+
+    ALLOCS.fetch_add(1, Ordering::Relaxed);
+    BYTES.fetch_add(size as u64, Ordering::Relaxed);
+    let live = LIVE.fetch_add(size as i64, Ordering::Relaxed) + size as i64;
+    PEAK.fetch_max(live, Ordering::Relaxed);
+}
+
+fn freed(size: usize) {
+    if enabled() {
+        LIVE.fetch_sub(size as i64, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        freed(layout.size());
+    }
+
+    // Growing a Vec or String counts as another allocation of its new size.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            freed(layout.size());
+            allocated(new_size);
+        }
+        new_ptr
+    }
+}
+
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct AllocStats {
+    pub allocs: u64,
+    pub bytes: u64,
+    // The most heap live at once beyond what was live when the phase started.
+    pub peak_bytes: u64,
+}
+
+// Runs `f`, along with what it allocated if counting is enabled.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if !enabled() {
+        return (f(), None);
+    }
+
+    let allocs = ALLOCS.load(Ordering::Relaxed);
+    let bytes = BYTES.load(Ordering::Relaxed);
+    let live = LIVE.load(Ordering::Relaxed);
+    PEAK.store(live, Ordering::Relaxed);
+
+    let out = f();
+
+    let stats = AllocStats {
+        allocs: ALLOCS.load(Ordering::Relaxed) - allocs,
+        bytes: BYTES.load(Ordering::Relaxed) - bytes,
+        peak_bytes: (PEAK.load(Ordering::Relaxed) - live).max(0) as u64,
+    };
+    (out, Some(stats))
+}
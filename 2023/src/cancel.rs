@@ -0,0 +1,65 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+// Asks a long running part to give up. Parts check it every so often in their hot
+// loops and return early once it is set, either by hand or by its deadline passing.
+#[derive(Debug)]
+pub struct Cancel {
+    cancelled: AtomicBool,
+    deadline: Option<Instant>,
+}
+
+impl Cancel {
+    pub const fn never() -> Self {
+        Cancel {
+            cancelled: AtomicBool::new(false),
+            deadline: None,
+        }
+    }
+
+    pub fn after(timeout: Duration) -> Self {
+        Cancel {
+            cancelled: AtomicBool::new(false),
+            deadline: Some(Instant::now() + timeout),
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        if self.cancelled.load(Ordering::Relaxed) {
+            return true;
+        }
+
+        let expired = self.deadline.is_some_and(|d| Instant::now() >= d);
+        if expired {
+            self.cancel();
+        }
+        expired
+    }
+}
+
+impl Default for Cancel {
+    fn default() -> Self {
+        Self::never()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cancel::Cancel;
+    use std::time::Duration;
+
+    #[test]
+    fn test_cancel() {
+        let never = Cancel::never();
+        assert!(!never.is_cancelled());
+        never.cancel();
+        assert!(never.is_cancelled());
+
+        assert!(Cancel::after(Duration::ZERO).is_cancelled());
+        assert!(!Cancel::after(Duration::from_secs(3600)).is_cancelled());
+    }
+}
@@ -0,0 +1,297 @@
+use crate::solution::{Answer, Context, Solution};
+use crate::{parse_lines, BadLine};
+use std::collections::{HashMap, VecDeque};
+
+pub const ENGLISH: [(&str, u32); 10] = [
+    ("zero", 0),
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+pub const GERMAN: [(&str, u32); 10] = [
+    ("null", 0),
+    ("eins", 1),
+    ("zwei", 2),
+    ("drei", 3),
+    ("vier", 4),
+    ("fünf", 5),
+    ("sechs", 6),
+    ("sieben", 7),
+    ("acht", 8),
+    ("neun", 9),
+];
+
+pub const SPANISH: [(&str, u32); 10] = [
+    ("cero", 0),
+    ("uno", 1),
+    ("dos", 2),
+    ("tres", 3),
+    ("cuatro", 4),
+    ("cinco", 5),
+    ("seis", 6),
+    ("siete", 7),
+    ("ocho", 8),
+    ("nueve", 9),
+];
+
+pub fn digits(line: &str) -> Vec<u32> {
+    line.chars()
+        .filter(|c| c.is_ascii_digit())
+        .map(|c| c.to_digit(10).unwrap())
+        .collect()
+}
+
+// Both parts stop at the first line without a digit, reporting it.
+pub fn part_1(lines: impl IntoIterator<Item = impl AsRef<str>>) -> Result<u64, BadLine> {
+    parse_lines(lines, |line| {
+        let ds = digits(line);
+        Some((ds.first()? * 10 + ds.last()?) as u64)
+    })
+    .sum()
+}
+
+pub fn part_2(
+    lines: impl IntoIterator<Item = impl AsRef<str>>,
+    matcher: &DigitMatcher,
+) -> Result<u64, BadLine> {
+    parse_lines(lines, |line| {
+        let (first, last) = matcher.first_last(line)?;
+        Some((first * 10 + last) as u64)
+    })
+    .sum()
+}
+
+// Finds the first and last digit of a line, spelled out or not, scanning inwards
+// from each end. Words may overlap, as in "eightwo".
+pub struct DigitMatcher {
+    pub forward: Automaton,
+    pub backward: Automaton,
+}
+
+impl DigitMatcher {
+    pub fn new(tables: &[&[(&str, u32)]]) -> Self {
+        let mut patterns: Vec<(Vec<u8>, u32)> =
+            (0..10).map(|d| (d.to_string().into_bytes(), d)).collect();
+        for table in tables {
+            patterns.extend(table.iter().map(|&(word, d)| (word.as_bytes().to_vec(), d)));
+        }
+
+        let reversed: Vec<(Vec<u8>, u32)> = patterns
+            .iter()
+            .map(|(p, d)| (p.iter().rev().copied().collect(), *d))
+            .collect();
+
+        DigitMatcher {
+            forward: Automaton::new(&patterns),
+            backward: Automaton::new(&reversed),
+        }
+    }
+
+    pub fn first_last(&self, line: &str) -> Option<(u32, u32)> {
+        let first = self.forward.leftmost(line.bytes())?;
+        let last = self.backward.leftmost(line.bytes().rev())?;
+
+        Some((first, last))
+    }
+}
+
+// Aho-Corasick automaton over UTF-8 bytes, with the transitions precomputed for
+// every state so each input byte is a single table lookup.
+pub struct Automaton {
+    pub next: Vec<[usize; 256]>,
+    // (pattern length, digit) for every pattern ending in each state.
+    pub outputs: Vec<Vec<(usize, u32)>>,
+    pub max_len: usize,
+}
+
+impl Automaton {
+    pub fn new(patterns: &[(Vec<u8>, u32)]) -> Self {
+        let mut trie: Vec<HashMap<u8, usize>> = vec![HashMap::new()];
+        let mut outputs: Vec<Vec<(usize, u32)>> = vec![Vec::new()];
+
+        for (pattern, digit) in patterns {
+            let mut state = 0;
+            for &b in pattern {
+                state = match trie[state].get(&b) {
+                    Some(&child) => child,
+                    None => {
+                        trie.push(HashMap::new());
+                        outputs.push(Vec::new());
+                        let child = trie.len() - 1;
+                        trie[state].insert(b, child);
+                        child
+                    }
+                };
+            }
+            outputs[state].push((pattern.len(), *digit));
+        }
+
+        let mut next = vec![[0; 256]; trie.len()];
+        let mut fail = vec![0; trie.len()];
+        let mut queue = VecDeque::new();
+
+        for (&b, &child) in &trie[0] {
+            next[0][b as usize] = child;
+            queue.push_back(child);
+        }
+
+        while let Some(state) = queue.pop_front() {
+            let inherited = outputs[fail[state]].clone();
+            outputs[state].extend(inherited);
+
+            for b in 0..=u8::MAX {
+                match trie[state].get(&b) {
+                    Some(&child) => {
+                        fail[child] = next[fail[state]][b as usize];
+                        next[state][b as usize] = child;
+                        queue.push_back(child);
+                    }
+                    None => next[state][b as usize] = next[fail[state]][b as usize],
+                }
+            }
+        }
+
+        let max_len = patterns.iter().map(|(p, _)| p.len()).max().unwrap_or(0);
+
+        Automaton {
+            next,
+            outputs,
+            max_len,
+        }
+    }
+
+    // Digit of the match starting earliest, preferring the longest on ties. Matches
+    // are reported by their end, so keep scanning until no pattern could still start
+    // at or before the best one.
+    pub fn leftmost(&self, bytes: impl Iterator<Item = u8>) -> Option<u32> {
+        let mut state = 0;
+        let mut best: Option<(usize, usize, u32)> = None;
+
+        for (pos, b) in bytes.enumerate() {
+            if best.is_some_and(|(start, _, _)| pos >= start + self.max_len) {
+                break;
+            }
+
+            state = self.next[state][b as usize];
+            for &(len, digit) in &self.outputs[state] {
+                let start = pos + 1 - len;
+                if best.is_none_or(|(s, l, _)| start < s || (start == s && len > l)) {
+                    best = Some((start, len, digit));
+                }
+            }
+        }
+
+        best.map(|(_, _, digit)| digit)
+    }
+}
+
+pub struct Day1 {
+    lines: Vec<String>,
+}
+
+impl Solution for Day1 {
+    fn parse(input: &str) -> Self {
+        Day1 {
+            lines: input.lines().map(String::from).collect(),
+        }
+    }
+
+    fn part_1(&self, _ctx: &Context) -> Option<Answer> {
+        Some(part_1(&self.lines).unwrap().into())
+    }
+
+    fn part_2(&self, _ctx: &Context) -> Option<Answer> {
+        Some(
+            part_2(&self.lines, &DigitMatcher::new(&[&ENGLISH]))
+                .unwrap()
+                .into(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day1::{digits, part_1, part_2, DigitMatcher, ENGLISH, GERMAN, SPANISH};
+    use crate::{strategies, stream_lines, BadLine};
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_overlaps() {
+        let matcher = DigitMatcher::new(&[&ENGLISH]);
+
+        assert_eq!(Some((8, 2)), matcher.first_last("eightwo"));
+        assert_eq!(Some((2, 1)), matcher.first_last("xtwone"));
+        assert_eq!(Some((3, 3)), matcher.first_last("threeight3"));
+        assert_eq!(Some((1, 1)), matcher.first_last("one"));
+        assert_eq!(None, matcher.first_last("abc"));
+    }
+
+    #[test]
+    fn test_languages() {
+        let matcher = DigitMatcher::new(&[&GERMAN, &SPANISH]);
+
+        assert_eq!(Some((5, 9)), matcher.first_last("äfünfzehn-neun"));
+        assert_eq!(Some((2, 4)), matcher.first_last("ñdosiebe4"));
+        assert_eq!(Some((8, 7)), matcher.first_last("achtsiebenü"));
+
+        let custom = DigitMatcher::new(&[&[("uno", 1), ("unodos", 9)]]);
+        assert_eq!(Some((9, 1)), custom.first_last("xunodosuno"));
+    }
+
+    #[test]
+    fn test_streaming() {
+        let input = "two1nine\neightwothree\n".repeat(1000);
+        let rdr = std::io::Cursor::new(input.into_bytes());
+
+        let matcher = DigitMatcher::new(&[&ENGLISH]);
+        let lines = stream_lines(rdr).map(Result::unwrap);
+        assert_eq!(Ok(1000 * (29 + 83)), part_2(lines, &matcher));
+
+        // A line without a digit is reported rather than panicking.
+        let bad = Err(BadLine {
+            number: 2,
+            line: "abc".to_string(),
+        });
+        assert_eq!(bad, part_1(["1a2", "abc", "3"]));
+        assert_eq!(bad, part_2(["one", "abc"], &matcher));
+
+        // So is one that can't be read.
+        let rdr = std::io::Cursor::new(b"1\n\xff\n".to_vec());
+        let lines: Vec<_> = stream_lines(rdr).collect();
+        assert!(lines[0].is_ok() && lines[1].is_err());
+    }
+
+    fn naive_first_last(line: &str) -> (u32, u32) {
+        let mut found = Vec::new();
+        for (i, c) in line.char_indices() {
+            if let Some(d) = c.to_digit(10) {
+                found.push(d);
+                continue;
+            }
+            if let Some(&(_, d)) = ENGLISH.iter().find(|(word, _)| line[i..].starts_with(word)) {
+                found.push(d);
+            }
+        }
+
+        (found[0], *found.last().unwrap())
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_naive(line in strategies::calibration_line()) {
+            let matcher = DigitMatcher::new(&[&ENGLISH]);
+            prop_assert_eq!(Some(naive_first_last(&line)), matcher.first_last(&line));
+
+            let numeric = DigitMatcher::new(&[]).first_last(&line).unwrap();
+            let ds = digits(&line);
+            prop_assert_eq!((ds[0], *ds.last().unwrap()), numeric);
+        }
+    }
+}
@@ -0,0 +1,742 @@
+use crate::raster::Canvas;
+use crate::solution::{Answer, Context, Solution};
+use crate::visualize::{Frame, Shade, Visualize};
+use std::collections::HashSet;
+use std::fmt;
+use std::fs::File;
+use std::io::BufWriter;
+
+pub fn part_1(pipe_loop: &PipeLoop) -> usize {
+    pipe_loop.farthest().1
+}
+
+pub fn part_2(pipe_loop: &PipeLoop) -> usize {
+    pipe_loop.interior_count()
+}
+
+pub fn enclosed_by_parity(tiles: &[Vec<Tile>], loop_tiles: &HashSet<(usize, usize)>) -> usize {
+    classify(tiles, loop_tiles)
+        .iter()
+        .flatten()
+        .filter(|&&r| r == Region::Inside)
+        .count()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Region {
+    Loop,
+    Inside,
+    Outside,
+}
+
+pub fn classify(tiles: &[Vec<Tile>], loop_tiles: &HashSet<(usize, usize)>) -> Vec<Vec<Region>> {
+    let north_tiles: HashSet<Tile> =
+        HashSet::from_iter([Tile::Vertical, Tile::NEBend, Tile::WNBend]);
+
+    let mut out = Vec::new();
+
+    for (y, line) in tiles.iter().enumerate() {
+        let mut inside = false;
+        let mut row = Vec::new();
+        for (x, tile) in line.iter().enumerate() {
+            if loop_tiles.contains(&(x, y)) {
+                if north_tiles.contains(tile) {
+                    inside = !inside;
+                }
+                row.push(Region::Loop);
+                continue;
+            }
+
+            row.push(if inside {
+                Region::Inside
+            } else {
+                Region::Outside
+            });
+        }
+        out.push(row);
+    }
+
+    out
+}
+
+// Iterates the grid without its padding, yielding unpadded coordinates.
+pub fn unpadded(tiles: &[Vec<Tile>]) -> impl Iterator<Item = (usize, usize, Tile)> + '_ {
+    tiles[1..tiles.len() - 1]
+        .iter()
+        .enumerate()
+        .flat_map(|(y, line)| {
+            line[1..line.len() - 1]
+                .iter()
+                .enumerate()
+                .map(move |(x, &tile)| (x, y, tile))
+        })
+}
+
+pub fn render_text(tiles: &[Vec<Tile>], regions: &[Vec<Region>], colour: bool) -> String {
+    let mut out = String::new();
+    let width = tiles[0].len() - 2;
+
+    for (x, y, tile) in unpadded(tiles) {
+        let region = regions[y + 1][x + 1];
+        let glyph = match (region, tile) {
+            (Region::Loop, _) => tile.glyph(),
+            (Region::Inside, _) => 'I',
+            (Region::Outside, Tile::Ground) => ' ',
+            (Region::Outside, _) => tile.glyph(),
+        };
+
+        if colour {
+            let style = match region {
+                Region::Loop => "1;33",
+                Region::Inside => "1;42",
+                Region::Outside => "2",
+            };
+            out.push_str(&format!("\x1b[{style}m{glyph}\x1b[0m"));
+        } else {
+            out.push(glyph);
+        }
+
+        if x == width - 1 {
+            out.push('\n');
+        }
+    }
+
+    out
+}
+
+pub fn render_svg(tiles: &[Vec<Tile>], regions: &[Vec<Region>], cell: usize) -> String {
+    let width = (tiles[0].len() - 2) * cell;
+    let height = (tiles.len() - 2) * cell;
+    let half = cell / 2;
+
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\">\n"
+    );
+
+    for (x, y, tile) in unpadded(tiles) {
+        let region = regions[y + 1][x + 1];
+        let (cx, cy) = (x * cell + half, y * cell + half);
+
+        out.push_str(&format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{cell}\" height=\"{cell}\" fill=\"{}\"/>\n",
+            x * cell,
+            y * cell,
+            region.fill()
+        ));
+
+        for (dx, dy) in tile.directions() {
+            out.push_str(&format!(
+                "<line x1=\"{cx}\" y1=\"{cy}\" x2=\"{}\" y2=\"{}\" stroke=\"{}\" stroke-width=\"{}\"/>\n",
+                cx as isize + dx * half as isize,
+                cy as isize + dy * half as isize,
+                region.stroke(),
+                if region == Region::Loop { 3 } else { 1 },
+            ));
+        }
+    }
+
+    out.push_str("</svg>\n");
+    out
+}
+
+pub fn rasterize(
+    tiles: &[Vec<Tile>],
+    regions: &[Vec<Region>],
+    cell: usize,
+) -> (usize, usize, Vec<u8>) {
+    let mut canvas = Canvas::new(tiles[0].len() - 2, tiles.len() - 2, cell, [0; 3]);
+
+    for (x, y, tile) in unpadded(tiles) {
+        let region = regions[y + 1][x + 1];
+        canvas.fill_cell(x, y, region.fill_rgb());
+        canvas.stroke(x, y, &tile.directions(), region.stroke_rgb());
+    }
+
+    (canvas.width, canvas.height, canvas.data)
+}
+
+pub fn write_png(path: &str, tiles: &[Vec<Tile>], regions: &[Vec<Region>], cell: usize) {
+    let (width, height, data) = rasterize(tiles, regions, cell);
+
+    let file = File::create(path).unwrap();
+    let mut encoder = png::Encoder::new(BufWriter::new(file), width as u32, height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let mut writer = encoder.write_header().unwrap();
+    writer.write_image_data(&data).unwrap();
+}
+
+impl Region {
+    pub fn fill_rgb(&self) -> [u8; 3] {
+        match self {
+            Region::Loop => [0x20, 0x20, 0x20],
+            Region::Inside => [0x2e, 0x8b, 0x57],
+            Region::Outside => [0xf0, 0xf0, 0xf0],
+        }
+    }
+
+    pub fn stroke_rgb(&self) -> [u8; 3] {
+        match self {
+            Region::Loop => [0xff, 0xc0, 0x00],
+            Region::Inside | Region::Outside => [0xa0, 0xa0, 0xa0],
+        }
+    }
+
+    pub fn fill(&self) -> String {
+        let [r, g, b] = self.fill_rgb();
+        format!("#{r:02x}{g:02x}{b:02x}")
+    }
+
+    pub fn stroke(&self) -> String {
+        let [r, g, b] = self.stroke_rgb();
+        format!("#{r:02x}{g:02x}{b:02x}")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    UnknownTile { c: char, x: usize, y: usize },
+    MissingStart,
+    MultipleStarts,
+    DeadEnd,
+    AmbiguousStart(Vec<Tile>),
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GridError::UnknownTile { c, x, y } => write!(f, "unknown tile {c:?} at ({x}, {y})"),
+            GridError::MissingStart => write!(f, "no tile is S"),
+            GridError::MultipleStarts => write!(f, "more than one tile is S"),
+            GridError::DeadEnd => write!(f, "the pipe from S doesn't loop back to it"),
+            GridError::AmbiguousStart(tiles) => {
+                write!(f, "S could be any of {} tiles", tiles.len())
+            }
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+#[allow(clippy::type_complexity)]
+pub fn parse_grid(input: &str) -> Result<(Vec<Vec<Tile>>, (usize, usize)), GridError> {
+    let rowlen = input.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+    let mut tiles = vec![vec![Tile::Ground; rowlen + 2]];
+    let mut start = None;
+
+    for (y, line) in input.lines().enumerate() {
+        let mut row = vec![Tile::Ground];
+        for (x, c) in line.chars().enumerate() {
+            let tile = match c {
+                'S' => {
+                    if start.is_some() {
+                        return Err(GridError::MultipleStarts);
+                    }
+                    start = Some((x + 1, y + 1)); // take padding into account
+                    Tile::Ground
+                }
+                '.' => Tile::Ground,
+                '|' => Tile::Vertical,
+                '-' => Tile::Horizontal,
+                'L' => Tile::NEBend,
+                'J' => Tile::WNBend,
+                '7' => Tile::WSBend,
+                'F' => Tile::SEBend,
+                _ => return Err(GridError::UnknownTile { c, x, y }),
+            };
+            row.push(tile);
+        }
+
+        row.resize(rowlen + 2, Tile::Ground);
+        tiles.push(row);
+    }
+    tiles.push(vec![Tile::Ground; rowlen + 2]);
+
+    let start = start.ok_or(GridError::MissingStart)?;
+    replace_start(&mut tiles, start)?;
+
+    Ok((tiles, start))
+}
+
+pub fn find_loop(tiles: &[Vec<Tile>], start: (usize, usize)) -> HashSet<(usize, usize)> {
+    PipeLoop::trace(tiles, start)
+        .map(|l| l.path.into_iter().collect())
+        .unwrap_or_default()
+}
+
+pub fn find_all_loops(tiles: &[Vec<Tile>]) -> Vec<PipeLoop> {
+    let mut seen = HashSet::new();
+    let mut loops = Vec::new();
+
+    for (y, line) in tiles.iter().enumerate() {
+        for (x, tile) in line.iter().enumerate() {
+            if *tile == Tile::Ground || seen.contains(&(x, y)) {
+                continue;
+            }
+
+            match PipeLoop::trace(tiles, (x, y)) {
+                Some(pipe_loop) => {
+                    seen.extend(pipe_loop.path.iter().copied());
+                    loops.push(pipe_loop);
+                }
+                None => {
+                    seen.insert((x, y));
+                }
+            }
+        }
+    }
+
+    loops
+}
+
+pub fn replace_start(tiles: &mut [Vec<Tile>], start: (usize, usize)) -> Result<(), GridError> {
+    let (sx, sy) = start;
+
+    let closing: Vec<Tile> = Tile::PIPES
+        .into_iter()
+        .filter(|&candidate| {
+            tiles[sy][sx] = candidate;
+            PipeLoop::trace(tiles, start).is_some()
+        })
+        .collect();
+
+    match closing[..] {
+        [tile] => {
+            tiles[sy][sx] = tile;
+            Ok(())
+        }
+        [] => {
+            tiles[sy][sx] = Tile::Ground;
+            Err(GridError::DeadEnd)
+        }
+        _ => {
+            tiles[sy][sx] = Tile::Ground;
+            Err(GridError::AmbiguousStart(closing))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Winding {
+    Clockwise,
+    CounterClockwise,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipeLoop {
+    // Tiles in traversal order, starting at `S`. The closing edge back to `S` is implied.
+    pub path: Vec<(usize, usize)>,
+}
+
+impl PipeLoop {
+    // Follows pipes from `start`, returning `None` if the path dead-ends instead of closing.
+    pub fn trace(tiles: &[Vec<Tile>], start: (usize, usize)) -> Option<Self> {
+        let mut path = vec![start];
+        let mut prev = start;
+        let mut current = *tiles[start.1][start.0]
+            .connections(start.0, start.1)
+            .first()?;
+
+        loop {
+            let conns = tiles
+                .get(current.1)?
+                .get(current.0)?
+                .connections(current.0, current.1);
+            if !conns.contains(&prev) {
+                return None;
+            }
+            if current == start {
+                return Some(PipeLoop { path });
+            }
+
+            path.push(current);
+            let next = conns.into_iter().find(|&c| c != prev)?;
+            prev = current;
+            current = next;
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.path.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.path.is_empty()
+    }
+
+    pub fn farthest(&self) -> ((usize, usize), usize) {
+        let steps = self.len() / 2;
+
+        (self.path[steps], steps)
+    }
+
+    // Shoelace formula. Screen coordinates grow downwards, so a positive sum means clockwise.
+    pub fn signed_double_area(&self) -> i64 {
+        self.path
+            .iter()
+            .zip(self.path.iter().cycle().skip(1))
+            .map(|(&(x1, y1), &(x2, y2))| x1 as i64 * y2 as i64 - x2 as i64 * y1 as i64)
+            .sum()
+    }
+
+    pub fn area(&self) -> usize {
+        self.signed_double_area().unsigned_abs() as usize / 2
+    }
+
+    pub fn winding(&self) -> Winding {
+        if self.signed_double_area() > 0 {
+            Winding::Clockwise
+        } else {
+            Winding::CounterClockwise
+        }
+    }
+
+    // Pick's theorem: A = i + b/2 - 1, with every loop tile as a boundary point.
+    pub fn interior_count(&self) -> usize {
+        self.area() + 1 - self.len() / 2
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Tile {
+    Ground,
+    Vertical,
+    Horizontal,
+    NEBend,
+    WNBend,
+    WSBend,
+    SEBend,
+}
+
+impl Tile {
+    pub const PIPES: [Tile; 6] = [
+        Tile::Vertical,
+        Tile::Horizontal,
+        Tile::NEBend,
+        Tile::WNBend,
+        Tile::WSBend,
+        Tile::SEBend,
+    ];
+
+    pub fn glyph(&self) -> char {
+        match self {
+            Tile::Ground => '.',
+            Tile::Vertical => '│',
+            Tile::Horizontal => '─',
+            Tile::NEBend => '└',
+            Tile::WNBend => '┘',
+            Tile::WSBend => '┐',
+            Tile::SEBend => '┌',
+        }
+    }
+
+    pub fn directions(&self) -> Vec<(isize, isize)> {
+        self.connections(1, 1)
+            .into_iter()
+            .map(|(x, y)| (x as isize - 1, y as isize - 1))
+            .collect()
+    }
+
+    pub fn connections(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
+        match self {
+            Tile::Ground => Vec::new(),
+            Tile::Vertical => vec![(x, y - 1), (x, y + 1)],
+            Tile::Horizontal => vec![(x - 1, y), (x + 1, y)],
+            Tile::NEBend => vec![(x, y - 1), (x + 1, y)],
+            Tile::WNBend => vec![(x - 1, y), (x, y - 1)],
+            Tile::WSBend => vec![(x - 1, y), (x, y + 1)],
+            Tile::SEBend => vec![(x, y + 1), (x + 1, y)],
+        }
+    }
+}
+
+// Follows the loop a tile per frame from `S`, ending on the tiles it encloses.
+pub struct LoopWalk {
+    tiles: Vec<Vec<Tile>>,
+    pipe_loop: PipeLoop,
+}
+
+impl LoopWalk {
+    pub fn new(input: &str) -> Result<Self, GridError> {
+        let (tiles, start) = parse_grid(input)?;
+        let pipe_loop = PipeLoop::trace(&tiles, start).ok_or(GridError::DeadEnd)?;
+
+        Ok(LoopWalk { tiles, pipe_loop })
+    }
+
+    fn grid(&self, caption: String, shade: impl Fn(usize, usize, Tile) -> Shade) -> Frame {
+        let mut frame = Frame::new(caption);
+        for (x, y, tile) in unpadded(&self.tiles) {
+            if x == 0 {
+                frame.new_row();
+            }
+            let glyph = if tile == Tile::Ground {
+                '.'
+            } else {
+                tile.glyph()
+            };
+            frame.push_text(&glyph.to_string(), shade(x + 1, y + 1, tile));
+        }
+
+        frame
+    }
+}
+
+impl Visualize for LoopWalk {
+    fn frame_count(&self) -> usize {
+        self.pipe_loop.len() + 1
+    }
+
+    fn frame(&self, idx: usize) -> Frame {
+        let path = &self.pipe_loop.path;
+
+        if idx < path.len() {
+            let walked: HashSet<(usize, usize)> = path[..idx].iter().copied().collect();
+            let caption = format!("step {idx} of {} at {:?}", path.len(), path[idx]);
+
+            return self.grid(caption, |x, y, tile| {
+                if (x, y) == path[idx] {
+                    Shade::Active
+                } else if walked.contains(&(x, y)) {
+                    Shade::Marked
+                } else if tile == Tile::Ground {
+                    Shade::Dim
+                } else {
+                    Shade::Plain
+                }
+            });
+        }
+
+        let loop_tiles: HashSet<(usize, usize)> = path.iter().copied().collect();
+        let regions = classify(&self.tiles, &loop_tiles);
+        let caption = format!(
+            "loop of {} tiles, farthest {} steps away, {} enclosed",
+            path.len(),
+            self.pipe_loop.farthest().1,
+            self.pipe_loop.interior_count()
+        );
+
+        self.grid(caption, |x, y, tile| match regions[y][x] {
+            Region::Loop => Shade::Marked,
+            Region::Inside => Shade::Filled,
+            Region::Outside if tile == Tile::Ground => Shade::Dim,
+            Region::Outside => Shade::Plain,
+        })
+    }
+}
+
+pub struct Day10 {
+    pipe_loop: PipeLoop,
+}
+
+impl Solution for Day10 {
+    fn parse(input: &str) -> Self {
+        let (tiles, start) = parse_grid(input).unwrap();
+
+        Day10 {
+            pipe_loop: PipeLoop::trace(&tiles, start).unwrap(),
+        }
+    }
+
+    fn part_1(&self, _ctx: &Context) -> Option<Answer> {
+        Some(part_1(&self.pipe_loop).into())
+    }
+
+    fn part_2(&self, _ctx: &Context) -> Option<Answer> {
+        Some(part_2(&self.pipe_loop).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day10::{
+        classify, enclosed_by_parity, find_all_loops, find_loop, parse_grid, part_1, part_2,
+        rasterize, render_svg, render_text, GridError, LoopWalk, PipeLoop, Tile, Winding,
+    };
+    use crate::strategies;
+    use crate::visualize::{Shade, Visualize};
+    use indoc::indoc;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_parse_a() {
+        let input = indoc! {"
+            .....
+            .S-7.
+            .|.|.
+            .L-J.
+            .....
+        "};
+
+        let (grid, start) = parse_grid(input).unwrap();
+
+        println!("Start: {start:?}");
+        for line in &grid {
+            println!("{line:?}");
+        }
+
+        let l = find_loop(&grid, start);
+        for coords in l {
+            println!("{coords:?}");
+        }
+    }
+
+    #[test]
+    fn test_pipe_loop_geometry() {
+        let input = indoc! {"
+            .....
+            .S-7.
+            .|.|.
+            .L-J.
+            .....
+        "};
+
+        let (tiles, start) = parse_grid(input).unwrap();
+        let pipe_loop = PipeLoop::trace(&tiles, start).unwrap();
+
+        assert_eq!(
+            vec![
+                (2, 2),
+                (2, 3),
+                (2, 4),
+                (3, 4),
+                (4, 4),
+                (4, 3),
+                (4, 2),
+                (3, 2)
+            ],
+            pipe_loop.path
+        );
+        assert_eq!(4, pipe_loop.area());
+        assert_eq!(Winding::CounterClockwise, pipe_loop.winding());
+        assert_eq!(((4, 4), 4), pipe_loop.farthest());
+        assert_eq!(1, pipe_loop.interior_count());
+    }
+
+    // The examples check part 2's answers; parity has to agree with them.
+    #[test]
+    fn test_parity_matches_examples() {
+        for input in [
+            include_str!("../examples/day10/enclosed.txt"),
+            include_str!("../examples/day10/larger.txt"),
+        ] {
+            let (tiles, start) = parse_grid(input).unwrap();
+            let pipe_loop = PipeLoop::trace(&tiles, start).unwrap();
+            let loop_tiles = find_loop(&tiles, start);
+
+            assert_eq!(part_2(&pipe_loop), enclosed_by_parity(&tiles, &loop_tiles));
+        }
+    }
+
+    #[test]
+    fn test_start_errors() {
+        let dead_end = indoc! {"
+            .....
+            .S-7.
+            .|.|.
+            .L-..
+            .....
+        "};
+        assert_eq!(Err(GridError::DeadEnd), parse_grid(dead_end));
+
+        let ambiguous = indoc! {"
+            F-7..
+            |.|..
+            L-S-7
+            ..|.|
+            ..L-J
+        "};
+        assert_eq!(
+            Err(GridError::AmbiguousStart(vec![Tile::WNBend, Tile::SEBend])),
+            parse_grid(ambiguous).map(|_| ())
+        );
+
+        assert_eq!(
+            Err(GridError::UnknownTile { c: 'x', x: 2, y: 1 }),
+            parse_grid(".S-7\n.|x|\n.L-J\n").map(|_| ())
+        );
+        assert_eq!(Err(GridError::MissingStart), parse_grid("F7\nLJ\n"));
+    }
+
+    #[test]
+    fn test_find_all_loops() {
+        let input = indoc! {"
+            F7.F-7
+            LJ.|.|
+            S7.L-J
+            LJ....
+        "};
+
+        let (tiles, start) = parse_grid(input).unwrap();
+        let loops = find_all_loops(&tiles);
+
+        assert_eq!(3, loops.len());
+        assert_eq!(
+            vec![4, 8, 4],
+            loops.iter().map(|l| l.len()).collect::<Vec<_>>()
+        );
+        assert!(loops.iter().any(|l| l.path.contains(&start)));
+    }
+
+    #[test]
+    fn test_render() {
+        let input = indoc! {"
+            .....
+            .S-7.
+            .|.|.
+            .L-J.
+            .F7..
+        "};
+
+        let (tiles, start) = parse_grid(input).unwrap();
+        let regions = classify(&tiles, &find_loop(&tiles, start));
+
+        let expected = "     \n ┌─┐ \n │I│ \n └─┘ \n ┌┐  \n";
+        assert_eq!(expected, render_text(&tiles, &regions, false));
+        assert!(render_text(&tiles, &regions, true).contains("\x1b[1;42mI\x1b[0m"));
+
+        let svg = render_svg(&tiles, &regions, 10);
+        assert_eq!(25, svg.matches("<rect").count());
+        assert_eq!(20, svg.matches("<line").count());
+
+        let (width, height, data) = rasterize(&tiles, &regions, 4);
+        assert_eq!((20, 20), (width, height));
+        assert_eq!(20 * 20 * 3, data.len());
+    }
+
+    #[test]
+    fn test_loop_walk() {
+        let walk = LoopWalk::new(indoc! {"
+            .....
+            .S-7.
+            .|.|.
+            .L-J.
+            .....
+        "})
+        .unwrap();
+        assert_eq!(9, walk.frame_count());
+
+        let frame = walk.frame(2);
+        assert_eq!(".....\n.┌─┐.\n.│.│.\n.└─┘.\n.....\n", frame.text());
+        assert_eq!(Some((1, 3)), frame.focus());
+        assert_eq!(Shade::Marked, frame.rows[2][1].shade);
+        assert_eq!(Shade::Plain, frame.rows[3][2].shade);
+
+        let last = walk.frame(8);
+        assert_eq!(Shade::Filled, last.rows[2][2].shade);
+        assert!(last.caption.ends_with("1 enclosed"));
+    }
+
+    proptest! {
+        #[test]
+        fn test_pick_matches_parity(input in strategies::pipe_loop()) {
+            let (tiles, start) = parse_grid(&input).unwrap();
+            let pipe_loop = PipeLoop::trace(&tiles, start).unwrap();
+            let loop_tiles = find_loop(&tiles, start);
+
+            prop_assert_eq!(loop_tiles.len() / 2, part_1(&pipe_loop));
+            prop_assert_eq!(enclosed_by_parity(&tiles, &loop_tiles), part_2(&pipe_loop));
+        }
+    }
+}
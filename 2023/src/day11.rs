@@ -0,0 +1,459 @@
+use crate::solution::{Answer, Context, Solution};
+use crate::visualize::{Frame, Shade, Visualize};
+use std::io::{self, Write};
+
+pub fn part_1(input: &str) -> u128 {
+    let galaxies = find_galaxies(input, 2);
+
+    find_distance(&galaxies)
+}
+
+pub fn part_2(input: &str, expand_factor: u128) -> u128 {
+    let galaxies = find_galaxies(input, expand_factor);
+
+    find_distance(&galaxies)
+}
+
+pub fn find_distance(galaxies: &[(u128, u128)]) -> u128 {
+    let rows: Vec<u128> = galaxies.iter().map(|g| g.0).collect();
+    let cols: Vec<u128> = galaxies.iter().map(|g| g.1).collect();
+
+    axis_distance(rows) + axis_distance(cols)
+}
+
+// Sum of |a - b| over all pairs: once sorted, each coordinate is the larger one in
+// exactly `idx` pairs, contributing `c * idx` minus everything before it.
+pub fn axis_distance(mut coords: Vec<u128>) -> u128 {
+    coords.sort_unstable();
+
+    let mut prefix = 0;
+    let mut total = 0;
+    for (idx, &c) in coords.iter().enumerate() {
+        total += c * idx as u128 - prefix;
+        prefix += c;
+    }
+
+    total
+}
+
+pub fn find_galaxies(input: &str, expand_factor: u128) -> Vec<(u128, u128)> {
+    find_galaxies_with(input, &|_| expand_factor, &|_| expand_factor)
+}
+
+pub fn find_galaxies_with(
+    input: &str,
+    row_rule: &dyn Fn(usize) -> u128,
+    col_rule: &dyn Fn(usize) -> u128,
+) -> Vec<(u128, u128)> {
+    Image::new(input).expanded(row_rule, col_rule)
+}
+
+// Galaxies where the image shows them, along with which rows and columns have a
+// galaxy in them, so it can be expanded by any factor without reading it again.
+// Anything other than a `#` counts as empty space.
+#[derive(Debug, Clone)]
+pub struct Image {
+    pub galaxies: Vec<(usize, usize)>,
+    pub occupied_rows: Vec<bool>,
+    pub occupied_cols: Vec<bool>,
+}
+
+impl Image {
+    pub fn new(input: &str) -> Self {
+        let mut galaxies = Vec::new();
+        let mut occupied_rows = Vec::new();
+        let mut occupied_cols = Vec::new();
+
+        for (row, line) in input.lines().enumerate() {
+            occupied_rows.push(false);
+            for (col, b) in line.bytes().enumerate() {
+                if b != b'#' {
+                    continue;
+                }
+                if col >= occupied_cols.len() {
+                    occupied_cols.resize(col + 1, false);
+                }
+                occupied_rows[row] = true;
+                occupied_cols[col] = true;
+                galaxies.push((row, col));
+            }
+        }
+
+        Image {
+            galaxies,
+            occupied_rows,
+            occupied_cols,
+        }
+    }
+
+    // Each rule maps the length of a run of consecutive empty lines to the number
+    // of lines every line in that run expands to.
+    pub fn expanded(
+        &self,
+        row_rule: &dyn Fn(usize) -> u128,
+        col_rule: &dyn Fn(usize) -> u128,
+    ) -> Vec<(u128, u128)> {
+        let row_pos = line_positions(&self.occupied_rows, row_rule);
+        let col_pos = line_positions(&self.occupied_cols, col_rule);
+
+        self.galaxies
+            .iter()
+            .map(|&(row, col)| (row_pos[row], col_pos[col]))
+            .collect()
+    }
+}
+
+// Expanded position of each line: the sum of the widths of all lines before it.
+pub fn line_positions(occupied: &[bool], rule: &dyn Fn(usize) -> u128) -> Vec<u128> {
+    let mut widths = vec![1; occupied.len()];
+
+    let mut idx = 0;
+    while idx < occupied.len() {
+        if occupied[idx] {
+            idx += 1;
+            continue;
+        }
+
+        let run = occupied[idx..].iter().take_while(|&&o| !o).count();
+        widths[idx..idx + run].fill(rule(run));
+        idx += run;
+    }
+
+    widths
+        .iter()
+        .scan(0, |pos, &width| {
+            let line = *pos;
+            *pos += width;
+            Some(line)
+        })
+        .collect()
+}
+
+pub fn manhattan_distance(p: (u128, u128), q: (u128, u128)) -> u128 {
+    p.0.abs_diff(q.0) + p.1.abs_diff(q.1)
+}
+
+// Sweeps outwards in row order from each galaxy, stopping once the row gap alone
+// exceeds the best distance found.
+pub fn nearest_neighbours(galaxies: &[(u128, u128)]) -> Vec<Option<(usize, u128)>> {
+    let mut order: Vec<usize> = (0..galaxies.len()).collect();
+    order.sort_unstable_by_key(|&i| galaxies[i]);
+
+    let mut out = vec![None; galaxies.len()];
+
+    for (rank, &i) in order.iter().enumerate() {
+        let g = galaxies[i];
+        let mut best: Option<(usize, u128)> = None;
+
+        let mut visit = |j: usize| {
+            if best.is_some_and(|(_, d)| g.0.abs_diff(galaxies[j].0) > d) {
+                return false;
+            }
+            let d = manhattan_distance(g, galaxies[j]);
+            if best.is_none_or(|(bj, bd)| (d, j) < (bd, bj)) {
+                best = Some((j, d));
+            }
+            true
+        };
+
+        for &j in order[..rank].iter().rev() {
+            if !visit(j) {
+                break;
+            }
+        }
+        for &j in &order[rank + 1..] {
+            if !visit(j) {
+                break;
+            }
+        }
+
+        out[i] = best;
+    }
+
+    out
+}
+
+pub fn distance_matrix(galaxies: &[(u128, u128)]) -> Vec<Vec<u128>> {
+    galaxies
+        .iter()
+        .map(|&p| galaxies.iter().map(|&q| manhattan_distance(p, q)).collect())
+        .collect()
+}
+
+pub fn write_distance_matrix(galaxies: &[(u128, u128)], out: &mut impl Write) -> io::Result<()> {
+    for row in distance_matrix(galaxies) {
+        let line: Vec<String> = row.iter().map(|d| d.to_string()).collect();
+        writeln!(out, "{}", line.join(","))?;
+    }
+
+    Ok(())
+}
+
+// Doubles the empty rows and then the empty columns one per frame, as part 1
+// does, ending on the sum of the distances in the expanded image.
+pub struct Expansion {
+    image: Vec<Vec<u8>>,
+    empty_rows: Vec<usize>,
+    empty_cols: Vec<usize>,
+    distances: u128,
+}
+
+impl Expansion {
+    pub fn new(input: &str) -> Self {
+        let image: Vec<Vec<u8>> = input.lines().map(|l| l.bytes().collect()).collect();
+        let width = image.iter().map(Vec::len).max().unwrap_or(0);
+
+        let empty_rows = (0..image.len())
+            .filter(|&r| !image[r].contains(&b'#'))
+            .collect();
+        let empty_cols = (0..width)
+            .filter(|&c| image.iter().all(|line| line.get(c) != Some(&b'#')))
+            .collect();
+
+        Expansion {
+            image,
+            empty_rows,
+            empty_cols,
+            distances: part_1(input),
+        }
+    }
+}
+
+impl Visualize for Expansion {
+    fn frame_count(&self) -> usize {
+        1 + self.empty_rows.len() + self.empty_cols.len()
+    }
+
+    fn frame(&self, idx: usize) -> Frame {
+        let rows = &self.empty_rows[..idx.min(self.empty_rows.len())];
+        let cols = &self.empty_cols[..idx.saturating_sub(rows.len()).min(self.empty_cols.len())];
+        let newest_row = (cols.is_empty() && idx > 0).then(|| rows[rows.len() - 1]);
+        let newest_col = cols.last().copied();
+
+        let galaxies = self.image.iter().flatten().filter(|&&b| b == b'#').count();
+        let mut caption = match (newest_row, newest_col) {
+            (Some(row), _) => format!("doubled row {row}"),
+            (_, Some(col)) => format!("doubled column {col}"),
+            _ => format!(
+                "{galaxies} galaxies, {} empty rows and {} empty columns",
+                self.empty_rows.len(),
+                self.empty_cols.len()
+            ),
+        };
+        if idx + 1 == self.frame_count() {
+            caption += &format!(", sum of distances {}", self.distances);
+        }
+
+        let inserted = |newest: bool| if newest { Shade::Active } else { Shade::Filled };
+        let mut frame = Frame::new(caption);
+        for (r, line) in self.image.iter().enumerate() {
+            let copies = if rows.contains(&r) { 2 } else { 1 };
+            for copy in 0..copies {
+                frame.new_row();
+                for (c, &b) in line.iter().enumerate() {
+                    let shade = match (copy, b) {
+                        (1, _) => inserted(newest_row == Some(r)),
+                        (_, b'#') => Shade::Marked,
+                        _ => Shade::Dim,
+                    };
+                    frame.push_text(&char::from(b).to_string(), shade);
+
+                    if cols.contains(&c) {
+                        let shade = match copy {
+                            1 => inserted(newest_row == Some(r)),
+                            _ => inserted(newest_col == Some(c)),
+                        };
+                        frame.push_text(".", shade);
+                    }
+                }
+            }
+        }
+        frame
+    }
+}
+
+// Each part expands the image by its own factor, so both start from the raw text.
+pub struct Day11 {
+    image: Image,
+}
+
+impl Solution for Day11 {
+    fn parse(input: &str) -> Self {
+        Day11 {
+            image: Image::new(input),
+        }
+    }
+
+    fn part_1(&self, _ctx: &Context) -> Option<Answer> {
+        let galaxies = self.image.expanded(&|_| 2, &|_| 2);
+        Some(find_distance(&galaxies).into())
+    }
+
+    fn part_2(&self, ctx: &Context) -> Option<Answer> {
+        let factor: u128 = ctx.param("factor").unwrap_or(1_000_000).into();
+        let galaxies = self.image.expanded(&|_| factor, &|_| factor);
+        Some(find_distance(&galaxies).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day11::{
+        distance_matrix, find_distance, find_galaxies, find_galaxies_with, manhattan_distance,
+        nearest_neighbours, part_1, part_2, write_distance_matrix, Expansion,
+    };
+    use crate::strategies;
+    use crate::visualize::{Shade, Visualize};
+    use indoc::indoc;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../examples/day11/example.txt");
+
+    #[test]
+    fn test_expansion() {
+        let expansion = Expansion::new(EXAMPLE);
+        assert_eq!(6, expansion.frame_count());
+        assert_eq!(EXAMPLE, expansion.frame(0).text());
+
+        let frame = expansion.frame(3);
+        assert_eq!("doubled column 2", frame.caption);
+        assert_eq!(Some((3, 0)), frame.focus());
+        assert_eq!(Shade::Filled, frame.rows[4][0].shade);
+
+        let last = expansion.frame(5);
+        assert!(last.caption.ends_with("sum of distances 374"));
+        assert_eq!(
+            indoc! {"
+                ....#........
+                .........#...
+                #............
+                .............
+                .............
+                ........#....
+                .#...........
+                ............#
+                .............
+                .............
+                .........#...
+                #....#.......
+            "},
+            last.text()
+        );
+    }
+
+    #[test]
+    fn test_stray_bytes_are_empty() {
+        // The row and column with only an `x` in them expand like any other empty
+        // one, in the visualization as in the parts.
+        let input = "#..\n.x.\n..#\r\n";
+        assert_eq!(6, part_1(input));
+
+        let expansion = Expansion::new(input);
+        assert_eq!(3, expansion.frame_count());
+        assert!(expansion.frame(2).caption.ends_with("sum of distances 6"));
+    }
+
+    #[test]
+    fn test_distance_matches_pairwise() {
+        let galaxies = find_galaxies(EXAMPLE, 1_000_000_000_000);
+
+        let pairwise: u128 = galaxies
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &p)| galaxies[i..].iter().map(move |&q| manhattan_distance(p, q)))
+            .sum();
+
+        assert_eq!(pairwise, find_distance(&galaxies));
+    }
+
+    #[test]
+    fn test_sparse_universe() {
+        // Galaxies on every other diagonal cell, so each one sits past `i` empty
+        // rows and columns and lands at `i * (factor + 1)` on both axes.
+        let n = 1_000u128;
+        let factor = 1_000_000_000_000u128;
+
+        let size = 2 * n as usize;
+        let mut input = String::with_capacity(size * (size + 1));
+        for row in 0..size {
+            for col in 0..size {
+                input.push(if row == col && row % 2 == 0 { '#' } else { '.' });
+            }
+            input.push('\n');
+        }
+
+        let expected = 2 * (factor + 1) * (n - 1) * n * (n + 1) / 6;
+        assert_eq!(expected, part_2(&input, factor));
+    }
+
+    #[test]
+    fn test_per_axis_expansion() {
+        let galaxies = find_galaxies_with(EXAMPLE, &|_| 10, &|_| 100);
+
+        assert_eq!((0, 2 + 100), galaxies[0]);
+        assert_eq!((8 - 2 + 2 * 10, 7 - 2 + 2 * 100), galaxies[6]);
+
+        let uniform = find_galaxies_with(EXAMPLE, &|_| 10, &|_| 10);
+        assert_eq!(find_galaxies(EXAMPLE, 10), uniform);
+    }
+
+    #[test]
+    fn test_run_length_expansion() {
+        let input = indoc! {"
+            #...#.#
+            .......
+            .......
+            #......
+        "};
+
+        // Lone empty lines double, longer runs triple.
+        let rule = |run: usize| if run == 1 { 2 } else { 3 };
+        let galaxies = find_galaxies_with(input, &rule, &rule);
+
+        assert_eq!(vec![(0, 0), (0, 10), (0, 13), (7, 0)], galaxies);
+    }
+
+    #[test]
+    fn test_queries() {
+        let galaxies = find_galaxies(EXAMPLE, 2);
+
+        assert_eq!(9, manhattan_distance(galaxies[4], galaxies[8]));
+        assert_eq!(15, manhattan_distance(galaxies[0], galaxies[6]));
+
+        let matrix = distance_matrix(&galaxies);
+        let nearest = nearest_neighbours(&galaxies);
+        for (i, row) in matrix.iter().enumerate() {
+            let (j, d) = nearest[i].unwrap();
+            assert_eq!(row[j], d);
+            assert_eq!(
+                d,
+                (0..galaxies.len())
+                    .filter(|&k| k != i)
+                    .map(|k| row[k])
+                    .min()
+                    .unwrap()
+            );
+        }
+
+        let mut csv = Vec::new();
+        write_distance_matrix(&galaxies, &mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(galaxies.len(), csv.lines().count());
+        assert!(csv.starts_with("0,6,6,9,"));
+    }
+
+    proptest! {
+        #[test]
+        fn test_prefix_sums_match_pairwise(input in strategies::galaxy_map(), factor in 1..1_000_000u128) {
+            let galaxies = find_galaxies(&input, factor);
+            let pairwise: u128 = galaxies
+                .iter()
+                .enumerate()
+                .flat_map(|(i, &p)| galaxies[i..].iter().map(move |&q| manhattan_distance(p, q)))
+                .sum();
+
+            prop_assert_eq!(pairwise, find_distance(&galaxies));
+            prop_assert_eq!(part_1(&input), part_2(&input, 2));
+        }
+    }
+}
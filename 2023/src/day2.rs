@@ -0,0 +1,313 @@
+use crate::solution::{Answer, Context, Solution};
+use crate::{parse_lines, BadLine};
+use nom::bytes::complete::tag;
+use nom::character::complete::{alpha1, digit1, space1};
+use nom::combinator::{all_consuming, map, map_res};
+use nom::multi::separated_list1;
+use nom::sequence::{preceded, separated_pair};
+use nom::IResult;
+use std::borrow::Borrow;
+use std::collections::HashMap;
+
+pub fn stream_games(
+    lines: impl IntoIterator<Item = impl AsRef<str>>,
+) -> impl Iterator<Item = Result<Game, BadLine>> {
+    parse_lines(lines, |line| {
+        all_consuming(parse_game)(line).ok().map(|(_, game)| game)
+    })
+}
+
+pub fn part_1(games: impl IntoIterator<Item = impl Borrow<Game>>, bag: &Bag) -> u64 {
+    bag.feasible_games(games).iter().map(|&id| id as u64).sum()
+}
+
+pub fn part_2(games: impl IntoIterator<Item = impl Borrow<Game>>) -> u64 {
+    games
+        .into_iter()
+        .map(|game| Bag::minimal([game]).power())
+        .sum()
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Bag {
+    pub cubes: HashMap<Color, u32>,
+}
+
+impl Bag {
+    pub fn new<'a>(cubes: impl IntoIterator<Item = (&'a str, u32)>) -> Self {
+        Bag {
+            cubes: cubes
+                .into_iter()
+                .map(|(color, count)| (color.to_string(), count))
+                .collect(),
+        }
+    }
+
+    pub fn standard() -> Self {
+        Bag::new([("red", 12), ("green", 13), ("blue", 14)])
+    }
+
+    // The smallest bag every round of every game could have been drawn from. A
+    // colour only ever drawn zero times isn't needed in it.
+    pub fn minimal(games: impl IntoIterator<Item = impl Borrow<Game>>) -> Self {
+        let mut bag = Bag::default();
+
+        for game in games {
+            for round in &game.borrow().rounds {
+                for (color, num) in round.totals() {
+                    if num > 0 {
+                        let current = bag.cubes.entry(color.clone()).or_insert(num);
+                        *current = (*current).max(num);
+                    }
+                }
+            }
+        }
+
+        bag
+    }
+
+    pub fn power(&self) -> u64 {
+        self.cubes.values().map(|&n| n as u64).product()
+    }
+
+    // Whether every round could have been drawn from this bag. Drawing none of a
+    // colour is possible even when the bag has none of it.
+    pub fn allows(&self, game: &Game) -> bool {
+        game.rounds.iter().all(|round| {
+            round
+                .totals()
+                .into_iter()
+                .all(|(color, num)| num <= self.cubes.get(color).copied().unwrap_or(0))
+        })
+    }
+
+    pub fn feasible_games(&self, games: impl IntoIterator<Item = impl Borrow<Game>>) -> Vec<u32> {
+        games
+            .into_iter()
+            .filter(|game| self.allows(game.borrow()))
+            .map(|game| game.borrow().id)
+            .collect()
+    }
+
+    // Chance that drawing as many cubes as the round shows, uniformly and without
+    // replacement, produces exactly those colour counts.
+    pub fn round_probability(&self, round: &Round) -> f64 {
+        let picks = round.totals();
+
+        let total: u32 = self.cubes.values().sum();
+        let drawn: u32 = picks.values().sum();
+        if drawn > total {
+            return 0.0;
+        }
+
+        let mut ways = 1.0;
+        for (color, num) in picks {
+            ways *= binomial(self.cubes.get(color).copied().unwrap_or(0), num);
+        }
+
+        ways / binomial(total, drawn)
+    }
+}
+
+pub fn binomial(n: u32, k: u32) -> f64 {
+    if k > n {
+        return 0.0;
+    }
+
+    let k = k.min(n - k);
+    (1..=k).fold(1.0, |acc, i| acc * (n - k + i) as f64 / i as f64)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub id: u32,
+    pub rounds: Vec<Round>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Round {
+    pub picks: Vec<(u32, Color)>,
+}
+
+impl Round {
+    // How many cubes of each colour the round drew, with a colour named twice
+    // counted as one pick of both amounts together.
+    pub fn totals(&self) -> HashMap<&Color, u32> {
+        let mut totals = HashMap::new();
+        for (num, color) in &self.picks {
+            *totals.entry(color).or_default() += num;
+        }
+        totals
+    }
+}
+
+pub type Color = String;
+
+pub fn parse_game(input: &str) -> IResult<&str, Game> {
+    map(
+        separated_pair(preceded(tag("Game "), parse_num), tag(": "), parse_rounds),
+        |(id, rounds)| Game { id, rounds },
+    )(input)
+}
+
+pub fn parse_rounds(input: &str) -> IResult<&str, Vec<Round>> {
+    separated_list1(tag("; "), parse_round)(input)
+}
+
+pub fn parse_round(input: &str) -> IResult<&str, Round> {
+    map(separated_list1(tag(", "), parse_pick), |picks| Round {
+        picks,
+    })(input)
+}
+
+pub fn parse_pick(input: &str) -> IResult<&str, (u32, Color)> {
+    separated_pair(parse_num, space1, parse_color)(input)
+}
+
+pub fn parse_color(input: &str) -> IResult<&str, Color> {
+    map(alpha1, String::from)(input)
+}
+
+pub fn parse_num(input: &str) -> IResult<&str, u32> {
+    map_res(digit1, |ds: &str| ds.parse())(input)
+}
+
+pub struct Day2 {
+    games: Vec<Game>,
+}
+
+impl Solution for Day2 {
+    fn parse(input: &str) -> Self {
+        Day2 {
+            games: stream_games(input.lines())
+                .collect::<Result<_, _>>()
+                .unwrap(),
+        }
+    }
+
+    fn part_1(&self, _ctx: &Context) -> Option<Answer> {
+        Some(part_1(&self.games, &Bag::standard()).into())
+    }
+
+    fn part_2(&self, _ctx: &Context) -> Option<Answer> {
+        Some(part_2(&self.games).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day2::{parse_round, part_1, part_2, stream_games, Bag, Game};
+    use crate::{strategies, stream_lines, BadLine};
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../examples/day2/example.txt");
+
+    #[test]
+    fn test_streaming() {
+        let rdr = std::io::Cursor::new(EXAMPLE.as_bytes());
+
+        let games = stream_games(stream_lines(rdr).map(Result::unwrap)).map(Result::unwrap);
+        assert_eq!(8, part_1(games, &Bag::standard()));
+
+        // A bad line is reported with its number, and the lines after it still parse.
+        let lines = ["Game 1: 3 blue", "Game two: 1 red", "Game 3: 1 red"];
+        let games: Vec<Result<Game, BadLine>> = stream_games(lines).collect();
+        assert_eq!(
+            Err(BadLine {
+                number: 2,
+                line: lines[1].to_string()
+            }),
+            games[1]
+        );
+        assert_eq!(3, games[2].as_ref().unwrap().id);
+    }
+
+    #[test]
+    fn test_bag_queries() {
+        let games: Vec<Game> = stream_games(EXAMPLE.lines()).map(Result::unwrap).collect();
+
+        assert_eq!(vec![1, 2, 5], Bag::standard().feasible_games(&games));
+        assert_eq!(
+            Bag::new([("red", 20), ("green", 13), ("blue", 15)]),
+            Bag::minimal(&games)
+        );
+        assert_eq!(
+            vec![1, 2, 3, 4, 5],
+            Bag::minimal(&games).feasible_games(&games)
+        );
+
+        let custom: Vec<Game> = stream_games(["Game 7: 2 teal, 1 magenta; 3 teal"].iter())
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(
+            Bag::new([("teal", 3), ("magenta", 1)]),
+            Bag::minimal(&custom)
+        );
+        assert!(Bag::standard().feasible_games(&custom).is_empty());
+
+        // A colour named twice in a round is drawn as both amounts together.
+        let repeated: Vec<Game> = stream_games(["Game 1: 7 red, 7 red; 3 blue"])
+            .map(Result::unwrap)
+            .collect();
+        assert!(!Bag::standard().allows(&repeated[0]));
+        assert_eq!(
+            Bag::new([("red", 14), ("blue", 3)]),
+            Bag::minimal(&repeated)
+        );
+
+        // None of a colour can be drawn from a bag without it, and the smallest bag
+        // doesn't need it.
+        let none: Vec<Game> = stream_games(["Game 1: 0 teal, 2 red"])
+            .map(Result::unwrap)
+            .collect();
+        assert!(Bag::standard().allows(&none[0]));
+        assert_eq!(Bag::new([("red", 2)]), Bag::minimal(&none));
+        assert_eq!(2, part_2(&none));
+    }
+
+    #[test]
+    fn test_round_probability() {
+        let bag = Bag::new([("red", 2), ("blue", 2)]);
+
+        let (_, round) = parse_round("1 red, 1 blue").unwrap();
+        assert!((bag.round_probability(&round) - 4.0 / 6.0).abs() < 1e-12);
+
+        let (_, round) = parse_round("2 red").unwrap();
+        assert!((bag.round_probability(&round) - 1.0 / 6.0).abs() < 1e-12);
+
+        let (_, round) = parse_round("3 red").unwrap();
+        assert_eq!(0.0, bag.round_probability(&round));
+
+        let (_, round) = parse_round("1 green").unwrap();
+        assert_eq!(0.0, bag.round_probability(&round));
+
+        // More cubes than the bag holds can't be drawn at all.
+        let (_, round) = parse_round("5 red").unwrap();
+        assert_eq!(0.0, bag.round_probability(&round));
+
+        // A repeated colour is the same as naming it once with the sum.
+        let (_, round) = parse_round("1 red, 1 red").unwrap();
+        assert!((bag.round_probability(&round) - 1.0 / 6.0).abs() < 1e-12);
+        let (_, round) = parse_round("1 red, 1 red, 1 red").unwrap();
+        assert_eq!(0.0, bag.round_probability(&round));
+    }
+
+    proptest! {
+        #[test]
+        fn test_bag_invariants(input in strategies::games()) {
+            let games: Vec<Game> = stream_games(input.lines()).map(Result::unwrap).collect();
+
+            let all: u64 = games.iter().map(|g| g.id as u64).sum();
+            prop_assert_eq!(all, part_1(&games, &Bag::minimal(&games)));
+
+            let standard = Bag::standard();
+            for game in &games {
+                let fits = game.rounds.iter().flat_map(|r| &r.picks).all(|(num, color)| {
+                    *num <= standard.cubes[color]
+                });
+                prop_assert_eq!(fits, standard.allows(game));
+                prop_assert!(Bag::minimal([game]).allows(game));
+            }
+        }
+    }
+}
@@ -0,0 +1,421 @@
+use crate::solution::{Answer, Context, Solution};
+use crate::visualize::{Frame, Shade, Visualize};
+use crate::BadLine;
+use std::collections::HashMap;
+use std::ops::Range;
+
+pub fn part_1(schematic: &Schematic) -> u32 {
+    schematic.part_numbers().map(|n| n.value).sum()
+}
+
+pub fn part_2(schematic: &Schematic) -> u64 {
+    schematic
+        .gears(2)
+        .map(|(_, parts)| parts.iter().map(|n| n.value as u64).product::<u64>())
+        .sum()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Number {
+    pub value: u32,
+    pub row: usize,
+    pub cols: Range<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symbol {
+    pub c: char,
+    pub row: usize,
+    pub col: usize,
+}
+
+// Numbers and symbols of a schematic, with the adjacency between them kept in both
+// directions by index. A line with a number too big for a u32 is rejected.
+#[derive(Debug, Clone)]
+pub struct Schematic {
+    pub numbers: Vec<Number>,
+    pub symbols: Vec<Symbol>,
+    pub number_symbols: Vec<Vec<usize>>,
+    pub symbol_numbers: Vec<Vec<usize>>,
+}
+
+impl Schematic {
+    pub fn new(chars: &[Vec<char>]) -> Result<Self, BadLine> {
+        let mut numbers = Vec::new();
+        let mut symbols = Vec::new();
+
+        for (row, line) in chars.iter().enumerate() {
+            let mut current: Option<Number> = None;
+            for (col, &c) in line.iter().enumerate() {
+                if let Some(digit) = c.to_digit(10) {
+                    let number = current.get_or_insert(Number {
+                        value: 0,
+                        row,
+                        cols: col..col,
+                    });
+                    number.value = number
+                        .value
+                        .checked_mul(10)
+                        .and_then(|value| value.checked_add(digit))
+                        .ok_or_else(|| BadLine {
+                            number: row + 1,
+                            line: line.iter().collect(),
+                        })?;
+                    number.cols.end = col + 1;
+                    continue;
+                }
+
+                numbers.extend(current.take());
+                if c != '.' {
+                    symbols.push(Symbol { c, row, col });
+                }
+            }
+            numbers.extend(current);
+        }
+
+        let positions: HashMap<(usize, usize), usize> = symbols
+            .iter()
+            .enumerate()
+            .map(|(idx, s)| ((s.row, s.col), idx))
+            .collect();
+
+        let mut number_symbols = vec![Vec::new(); numbers.len()];
+        let mut symbol_numbers = vec![Vec::new(); symbols.len()];
+
+        for (n, number) in numbers.iter().enumerate() {
+            for row in number.row.saturating_sub(1)..=number.row + 1 {
+                for col in number.cols.start.saturating_sub(1)..=number.cols.end {
+                    if let Some(&s) = positions.get(&(row, col)) {
+                        number_symbols[n].push(s);
+                        symbol_numbers[s].push(n);
+                    }
+                }
+            }
+        }
+
+        Ok(Schematic {
+            numbers,
+            symbols,
+            number_symbols,
+            symbol_numbers,
+        })
+    }
+
+    pub fn symbols_of(&self, n: usize) -> impl Iterator<Item = &Symbol> {
+        self.number_symbols[n].iter().map(|&s| &self.symbols[s])
+    }
+
+    pub fn numbers_of(&self, s: usize) -> impl Iterator<Item = &Number> {
+        self.symbol_numbers[s].iter().map(|&n| &self.numbers[n])
+    }
+
+    pub fn part_numbers(&self) -> impl Iterator<Item = &Number> {
+        self.numbers_where(|n| !self.number_symbols[n].is_empty())
+    }
+
+    pub fn numbers_adjacent_to(&self, c: char) -> impl Iterator<Item = &Number> {
+        self.numbers_where(move |n| self.symbols_of(n).any(|s| s.c == c))
+    }
+
+    pub fn shared_numbers(&self) -> impl Iterator<Item = &Number> {
+        self.numbers_where(|n| self.number_symbols[n].len() > 1)
+    }
+
+    // `*` symbols adjacent to exactly `k` numbers, with those numbers.
+    pub fn gears(&self, k: usize) -> impl Iterator<Item = (&Symbol, Vec<&Number>)> {
+        self.symbols
+            .iter()
+            .enumerate()
+            .filter(move |&(s, symbol)| symbol.c == '*' && self.symbol_numbers[s].len() == k)
+            .map(|(s, symbol)| (symbol, self.numbers_of(s).collect()))
+    }
+
+    pub fn numbers_where<'a>(
+        &'a self,
+        pred: impl Fn(usize) -> bool + 'a,
+    ) -> impl Iterator<Item = &'a Number> {
+        self.numbers
+            .iter()
+            .enumerate()
+            .filter(move |&(n, _)| pred(n))
+            .map(|(_, number)| number)
+    }
+}
+
+// Checks the numbers one per frame in reading order, keeping the ones next to a
+// symbol, and ends on all the part numbers with their sum.
+pub struct PartScan {
+    chars: Vec<Vec<char>>,
+    schematic: Schematic,
+}
+
+impl PartScan {
+    pub fn new(input: &str) -> Result<Self, BadLine> {
+        let chars: Vec<Vec<char>> = input.lines().map(|l| l.chars().collect()).collect();
+        let schematic = Schematic::new(&chars)?;
+
+        Ok(PartScan { chars, schematic })
+    }
+}
+
+impl Visualize for PartScan {
+    fn frame_count(&self) -> usize {
+        self.schematic.numbers.len() + 1
+    }
+
+    fn frame(&self, idx: usize) -> Frame {
+        let numbers = &self.schematic.numbers;
+        let sum: u32 = (0..numbers.len().min(idx + 1))
+            .filter(|&n| !self.schematic.number_symbols[n].is_empty())
+            .map(|n| numbers[n].value)
+            .sum();
+
+        let caption = match numbers.get(idx) {
+            Some(number) => match self.schematic.symbols_of(idx).next() {
+                Some(symbol) => format!("{} is next to '{}', sum {sum}", number.value, symbol.c),
+                None => format!("{} isn't next to a symbol, sum {sum}", number.value),
+            },
+            None => format!("sum of part numbers {sum}"),
+        };
+
+        let mut shades: Vec<Vec<Shade>> = self
+            .chars
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .map(|&c| if c == '.' { Shade::Dim } else { Shade::Plain })
+                    .collect()
+            })
+            .collect();
+        for (n, number) in numbers.iter().enumerate().take(idx) {
+            if !self.schematic.number_symbols[n].is_empty() {
+                shades[number.row][number.cols.clone()].fill(Shade::Marked);
+            }
+        }
+        if let Some(number) = numbers.get(idx) {
+            shades[number.row][number.cols.clone()].fill(Shade::Active);
+            for symbol in self.schematic.symbols_of(idx) {
+                shades[symbol.row][symbol.col] = Shade::Filled;
+            }
+        }
+
+        let mut frame = Frame::new(caption);
+        for (line, shades) in self.chars.iter().zip(shades) {
+            frame.new_row();
+            for (c, shade) in line.iter().zip(shades) {
+                frame.push_text(&c.to_string(), shade);
+            }
+        }
+        frame
+    }
+}
+
+pub struct Day3 {
+    schematic: Schematic,
+}
+
+impl Solution for Day3 {
+    fn parse(input: &str) -> Self {
+        let chars: Vec<Vec<char>> = input.lines().map(|l| l.chars().collect()).collect();
+
+        Day3 {
+            schematic: Schematic::new(&chars).unwrap(),
+        }
+    }
+
+    fn part_1(&self, _ctx: &Context) -> Option<Answer> {
+        Some(part_1(&self.schematic).into())
+    }
+
+    fn part_2(&self, _ctx: &Context) -> Option<Answer> {
+        Some(part_2(&self.schematic).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day3::{part_1, part_2, PartScan, Schematic};
+    use crate::visualize::{Shade, Visualize};
+    use crate::{strategies, BadLine};
+    use indoc::indoc;
+    use proptest::prelude::*;
+    use std::collections::{HashMap, HashSet};
+
+    const EXAMPLE: &str = include_str!("../examples/day3/example.txt");
+
+    #[test]
+    fn test_schematic_queries() {
+        let input = indoc! {"
+            12*..
+            ..#.3
+            *5..*
+            ..%..
+        "};
+        let chars: Vec<Vec<char>> = input.lines().map(|l| l.chars().collect()).collect();
+        let schematic = Schematic::new(&chars).unwrap();
+
+        assert_eq!(
+            vec![12, 5],
+            schematic
+                .numbers_adjacent_to('#')
+                .map(|n| n.value)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![12, 5],
+            schematic
+                .shared_numbers()
+                .map(|n| n.value)
+                .collect::<Vec<_>>()
+        );
+
+        let gears: Vec<(usize, usize, Vec<u32>)> = schematic
+            .gears(1)
+            .map(|(s, ns)| (s.row, s.col, ns.iter().map(|n| n.value).collect()))
+            .collect();
+        assert_eq!(
+            vec![(0, 2, vec![12]), (2, 0, vec![5]), (2, 4, vec![3])],
+            gears
+        );
+        assert_eq!(0, schematic.gears(2).count());
+    }
+
+    #[test]
+    fn test_number_touching_two_gears() {
+        let input = indoc! {"
+            2...3
+            .*.*.
+            ..7..
+        "};
+        let chars: Vec<Vec<char>> = input.lines().map(|l| l.chars().collect()).collect();
+
+        assert_eq!(2 * 7 + 3 * 7, part_2(&Schematic::new(&chars).unwrap()));
+    }
+
+    #[test]
+    fn test_part_scan() {
+        let scan = PartScan::new(EXAMPLE).unwrap();
+        assert_eq!(11, scan.frame_count());
+
+        // 114 isn't a part number, so it's left as it was once 35 is being checked.
+        let frame = scan.frame(2);
+        assert_eq!("35 is next to '*', sum 502", frame.caption);
+        assert_eq!(Shade::Marked, frame.rows[0][0].shade);
+        assert_eq!(Shade::Plain, frame.rows[0][5].shade);
+        assert_eq!(Some((2, 2)), frame.focus());
+        assert_eq!(Shade::Filled, frame.rows[1][3].shade);
+
+        assert_eq!("sum of part numbers 4361", scan.frame(10).caption);
+    }
+
+    fn parse(input: &str) -> Schematic {
+        let chars: Vec<Vec<char>> = input.lines().map(|l| l.chars().collect()).collect();
+        Schematic::new(&chars).unwrap()
+    }
+
+    #[test]
+    fn test_boundaries() {
+        // Number at the end of a line, touching a symbol at the start of the next one.
+        assert_eq!(12, part_1(&parse("..12\n...*\n")));
+        assert_eq!(12, part_1(&parse("12\n..*\n")));
+
+        // Ragged rows: symbols past the end of the first row still count.
+        assert_eq!(7, part_1(&parse("7\n.#\n")));
+        assert_eq!(0, part_1(&parse("7\n..#\n")));
+        assert_eq!(35, part_1(&parse("..\n.....35\n....$\n")));
+
+        // Width one.
+        assert_eq!(17, part_1(&parse("8\n*\n9\n")));
+        assert_eq!(72, part_2(&parse("8\n*\n9\n")));
+        assert_eq!(0, part_1(&parse("8\n.\n*\n")));
+
+        // A symbol is never counted twice for the same number.
+        assert_eq!(0, part_2(&parse("44\n*.\n")));
+    }
+
+    #[test]
+    fn test_number_overflow() {
+        assert_eq!(4294967295, part_1(&parse("4294967295*\n")));
+
+        let chars: Vec<Vec<char>> = ["..1", "12345678901*"]
+            .iter()
+            .map(|l| l.chars().collect())
+            .collect();
+        assert_eq!(
+            Err(BadLine {
+                number: 2,
+                line: "12345678901*".to_string()
+            }),
+            Schematic::new(&chars).map(|_| ())
+        );
+    }
+
+    // Checks every neighbour of every digit directly, as the reference for the
+    // bounding-box scan in `Schematic::new`.
+    fn naive(chars: &[Vec<char>]) -> (u32, u64) {
+        let cell = |y: isize, x: isize| -> Option<char> {
+            let row = chars.get(usize::try_from(y).ok()?)?;
+            row.get(usize::try_from(x).ok()?).copied()
+        };
+
+        let mut part_1 = 0;
+        let mut stars: HashMap<(isize, isize), Vec<u32>> = HashMap::new();
+
+        for (y, line) in chars.iter().enumerate() {
+            let mut x = 0;
+            while x < line.len() {
+                if !line[x].is_ascii_digit() {
+                    x += 1;
+                    continue;
+                }
+
+                let start = x;
+                while x < line.len() && line[x].is_ascii_digit() {
+                    x += 1;
+                }
+                let value: u32 = line[start..x].iter().collect::<String>().parse().unwrap();
+
+                let mut touching = HashSet::new();
+                for dx in start..x {
+                    for ny in y as isize - 1..=y as isize + 1 {
+                        for nx in dx as isize - 1..=dx as isize + 1 {
+                            match cell(ny, nx) {
+                                Some(c) if !c.is_ascii_digit() && c != '.' => {
+                                    touching.insert((ny, nx, c));
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+
+                if !touching.is_empty() {
+                    part_1 += value;
+                }
+                for (ny, nx, c) in touching {
+                    if c == '*' {
+                        stars.entry((ny, nx)).or_default().push(value);
+                    }
+                }
+            }
+        }
+
+        let part_2 = stars
+            .values()
+            .filter(|vs| vs.len() == 2)
+            .map(|vs| vs[0] as u64 * vs[1] as u64)
+            .sum();
+
+        (part_1, part_2)
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_naive(input in strategies::schematic()) {
+            let chars: Vec<Vec<char>> = input.lines().map(|l| l.chars().collect()).collect();
+            let schematic = Schematic::new(&chars).unwrap();
+
+            prop_assert_eq!(naive(&chars), (part_1(&schematic), part_2(&schematic)));
+        }
+    }
+}
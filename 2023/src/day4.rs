@@ -0,0 +1,212 @@
+use crate::solution::{Answer, Context, Solution};
+use crate::{parse_lines, BadLine};
+use nom::bytes::complete::tag;
+use nom::character::complete::{digit1, space1};
+use nom::combinator::{all_consuming, map, map_res};
+use nom::multi::separated_list1;
+use nom::sequence::{delimited, pair, preceded, tuple};
+use nom::IResult;
+use num::{BigUint, One, Zero};
+use std::borrow::Borrow;
+use std::collections::HashSet;
+
+pub fn stream_cards(
+    lines: impl IntoIterator<Item = impl AsRef<str>>,
+) -> impl Iterator<Item = Result<Card, BadLine>> {
+    parse_lines(lines, |line| {
+        all_consuming(parse_card)(line).ok().map(|(_, card)| card)
+    })
+}
+
+// A card's points double with each match past the first, so they outgrow a u64
+// once it has more than 64 matches.
+pub fn part_1(cards: impl IntoIterator<Item = impl Borrow<Card>>) -> BigUint {
+    cards
+        .into_iter()
+        .map(|card| match card.borrow().matches() {
+            0 => BigUint::zero(),
+            m => BigUint::one() << (m - 1),
+        })
+        .sum()
+}
+
+pub fn part_2(cards: impl IntoIterator<Item = impl Borrow<Card>>) -> BigUint {
+    let matches: Vec<usize> = cards.into_iter().map(|c| c.borrow().matches()).collect();
+
+    copy_counts(&matches).into_iter().sum()
+}
+
+// Copies held of each card once the cascade settles. Each copy of card `i` wins one
+// copy of each of the next `matches[i]` cards, so a running total of the copies still
+// being handed out gives every card's count in one pass.
+pub fn copy_counts(matches: &[usize]) -> Vec<BigUint> {
+    let mut counts = Vec::with_capacity(matches.len());
+    let mut running = BigUint::zero();
+    let mut expiring = vec![BigUint::zero(); matches.len() + 1];
+
+    for (i, &m) in matches.iter().enumerate() {
+        running -= &expiring[i];
+
+        let copies = &running + 1u32;
+        running += &copies;
+        expiring[(i + 1 + m).min(matches.len())] += &copies;
+
+        counts.push(copies);
+    }
+
+    counts
+}
+
+#[derive(Debug, Clone)]
+pub struct Card {
+    pub id: u32,
+    pub winning_nums: Vec<u32>,
+    pub have_nums: Vec<u32>,
+}
+
+impl Card {
+    pub fn matches(&self) -> usize {
+        if self
+            .winning_nums
+            .iter()
+            .chain(&self.have_nums)
+            .all(|&n| n < 128)
+        {
+            let mask = self.winning_nums.iter().fold(0u128, |m, &n| m | 1 << n);
+            self.have_nums
+                .iter()
+                .filter(|&&n| mask & (1 << n) != 0)
+                .count()
+        } else {
+            let winning: HashSet<u32> = self.winning_nums.iter().copied().collect();
+            self.have_nums
+                .iter()
+                .filter(|n| winning.contains(n))
+                .count()
+        }
+    }
+}
+
+pub fn parse_card(input: &str) -> IResult<&str, Card> {
+    map(
+        tuple((
+            delimited(pair(tag("Card"), space1), parse_num, tag(":")),
+            preceded(space1, parse_num_list),
+            preceded(delimited(space1, tag("|"), space1), parse_num_list),
+        )),
+        |(id, wins, haves)| Card {
+            id,
+            winning_nums: wins,
+            have_nums: haves,
+        },
+    )(input)
+}
+
+pub fn parse_num_list(input: &str) -> IResult<&str, Vec<u32>> {
+    separated_list1(space1, parse_num)(input)
+}
+
+pub fn parse_num(input: &str) -> IResult<&str, u32> {
+    map_res(digit1, |s: &str| s.parse())(input)
+}
+
+pub struct Day4 {
+    cards: Vec<Card>,
+}
+
+impl Solution for Day4 {
+    fn parse(input: &str) -> Self {
+        Day4 {
+            cards: stream_cards(input.lines())
+                .collect::<Result<_, _>>()
+                .unwrap(),
+        }
+    }
+
+    fn part_1(&self, _ctx: &Context) -> Option<Answer> {
+        Some(part_1(&self.cards).into())
+    }
+
+    fn part_2(&self, _ctx: &Context) -> Option<Answer> {
+        Some(part_2(&self.cards).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day4::{copy_counts, part_1, part_2, stream_cards, Card};
+    use crate::strategies;
+    use num::{BigUint, One};
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../examples/day4/example.txt");
+
+    #[test]
+    fn test_part1_many_matches() {
+        let nums: Vec<String> = (1..=70).map(|n| n.to_string()).collect();
+        let line = format!("Card 1: {} | {}", nums.join(" "), nums.join(" "));
+        let cards = stream_cards([line]).map(Result::unwrap);
+
+        assert_eq!(BigUint::one() << 69, part_1(cards));
+    }
+
+    #[test]
+    fn test_copy_counts() {
+        let matches: Vec<usize> = stream_cards(EXAMPLE.lines())
+            .map(|c| c.unwrap().matches())
+            .collect();
+
+        assert_eq!(vec![4, 2, 2, 1, 0, 0], matches);
+        assert_eq!(
+            vec![1u32, 2, 4, 8, 14, 1]
+                .into_iter()
+                .map(BigUint::from)
+                .collect::<Vec<_>>(),
+            copy_counts(&matches)
+        );
+    }
+
+    #[test]
+    fn test_exponential_cascade() {
+        // Every card wins a copy of every later card, doubling the count each time.
+        let n = 500;
+        let matches: Vec<usize> = (0..n).map(|i| n - 1 - i).collect();
+
+        let total: BigUint = copy_counts(&matches).into_iter().sum();
+        assert_eq!((BigUint::one() << n) - 1u32, total);
+    }
+
+    #[test]
+    fn test_matches_large_numbers() {
+        let card = stream_cards(["Card 1: 1 2 3 200 | 3 200 7 1"])
+            .next()
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(3, card.matches());
+    }
+
+    // Each card is itself plus everything its copies go on to win, counted from the
+    // back so every card it wins is already known.
+    fn naive_total(matches: &[usize]) -> usize {
+        let mut won = vec![1; matches.len()];
+        for card in (0..matches.len()).rev() {
+            won[card] += won[card + 1..=card + matches[card]].iter().sum::<usize>();
+        }
+
+        won.iter().sum()
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_naive(input in strategies::scratchcards()) {
+            let cards: Vec<Card> = stream_cards(input.lines()).map(Result::unwrap).collect();
+            let matches: Vec<usize> = cards.iter().map(Card::matches).collect();
+
+            let expected = BigUint::from(naive_total(&matches));
+
+            prop_assert_eq!(&expected, &part_2(&cards));
+            prop_assert_eq!(expected, copy_counts(&matches).into_iter().sum::<BigUint>());
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use crate::parallel;
+use crate::progress::Task;
+use crate::solution::{Answer, Context, Solution};
+use indicatif::HumanCount;
+use nom::bytes::complete::tag;
+use nom::character::complete::{alpha1, digit1, newline, space1};
+use nom::combinator::{map, map_opt, map_res};
+use nom::multi::separated_list1;
+use nom::sequence::{pair, preceded, separated_pair, terminated, tuple};
+use nom::IResult;
+use rayon::prelude::*;
+use std::ops::Range;
+
+pub fn part_1(input: &str) -> u64 {
+    let (_, (seeds, maps)) = parse_input(input).unwrap();
+
+    lowest_location(&seeds, &maps)
+}
+
+pub fn lowest_location(seeds: &[u64], maps: &[RangeMap]) -> u64 {
+    let mut min = u64::MAX;
+    for &seed in seeds {
+        min = min.min(maps.iter().fold(seed, |seed, map| map.mapped(seed)));
+    }
+    min
+}
+
+// Seeds are mapped in chunks on the shared pool rather than a thread per range.
+const CHUNK: u64 = 1 << 16;
+
+// None if `ctx.cancel` is set before every chunk has been mapped.
+pub fn part_2(input: &str, ctx: &Context) -> Option<u64> {
+    let (_, (seeds, maps)) = parse_input_2(input).unwrap();
+
+    lowest_in_ranges(&seeds, &maps, ctx)
+}
+
+pub fn lowest_in_ranges(seeds: &[Range<u64>], maps: &[RangeMap], ctx: &Context) -> Option<u64> {
+    let tasks: Vec<Box<dyn Task>> = seeds
+        .iter()
+        .enumerate()
+        .map(|(idx, seed_range)| {
+            ctx.progress.start(
+                format!("Range {}", idx + 1),
+                seed_range.end - seed_range.start,
+            )
+        })
+        .collect();
+
+    let chunk_mins: Vec<(usize, u64)> = parallel::par_chunks(seeds, CHUNK)
+        .map(|(idx, chunk)| {
+            if ctx.cancel.is_cancelled() {
+                return None;
+            }
+
+            let len = chunk.end - chunk.start;
+            let min = chunk
+                .map(|seed| maps.iter().fold(seed, |seed, map| map.mapped(seed)))
+                .min()
+                .unwrap_or(u64::MAX);
+
+            tasks[idx].inc(len);
+            Some((idx, min))
+        })
+        .collect::<Option<_>>()?;
+
+    let mut mins = vec![u64::MAX; seeds.len()];
+    for (idx, min) in chunk_mins {
+        mins[idx] = mins[idx].min(min);
+    }
+    for (task, min) in tasks.iter().zip(&mins) {
+        task.finish(format!("min value {}", HumanCount(*min)));
+    }
+
+    Some(mins.into_iter().min().unwrap_or(u64::MAX))
+}
+
+#[derive(Debug, Clone)]
+pub struct RangeMap {
+    pub mappings: Vec<Mapping>,
+}
+
+impl RangeMap {
+    pub fn mapped(&self, num: u64) -> u64 {
+        for range in &self.mappings {
+            if let Some(out) = range.mapped(num) {
+                return out;
+            }
+        }
+
+        num
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Mapping {
+    pub src: u64,
+    pub dest: u64,
+    pub len: u64,
+}
+
+impl Mapping {
+    // Compares offsets rather than range ends, which may not fit in a u64.
+    pub fn mapped(&self, num: u64) -> Option<u64> {
+        let diff = num.checked_sub(self.src)?;
+        if diff >= self.len {
+            return None;
+        }
+
+        self.dest.checked_add(diff)
+    }
+}
+
+pub fn parse_input(input: &str) -> IResult<&str, (Vec<u64>, Vec<RangeMap>)> {
+    pair(terminated(parse_seeds, tag("\n\n")), parse_range_maps)(input)
+}
+
+#[allow(clippy::type_complexity)]
+pub fn parse_input_2(input: &str) -> IResult<&str, (Vec<Range<u64>>, Vec<RangeMap>)> {
+    pair(terminated(parse_seeds_2, tag("\n\n")), parse_range_maps)(input)
+}
+
+pub fn parse_seeds(input: &str) -> IResult<&str, Vec<u64>> {
+    preceded(tag("seeds: "), separated_list1(space1, parse_num))(input)
+}
+
+pub fn parse_seeds_2(input: &str) -> IResult<&str, Vec<Range<u64>>> {
+    preceded(
+        tag("seeds: "),
+        separated_list1(
+            space1,
+            map_opt(separated_pair(parse_num, space1, parse_num), |(a, b)| {
+                seed_range(a, b)
+            }),
+        ),
+    )(input)
+}
+
+// None if the range runs past `u64::MAX`.
+pub fn seed_range(start: u64, len: u64) -> Option<Range<u64>> {
+    Some(start..start.checked_add(len)?)
+}
+
+pub fn parse_range_maps(input: &str) -> IResult<&str, Vec<RangeMap>> {
+    separated_list1(newline, parse_range_map)(input)
+}
+
+pub fn parse_range_map(input: &str) -> IResult<&str, RangeMap> {
+    map(
+        preceded(
+            pair(separated_pair(alpha1, tag("-to-"), alpha1), tag(" map:\n")),
+            terminated(separated_list1(newline, parse_mapping), newline),
+        ),
+        |ranges: Vec<Mapping>| RangeMap { mappings: ranges },
+    )(input)
+}
+
+// Rejects mappings whose source or destination range runs past `u64::MAX`.
+pub fn parse_mapping(input: &str) -> IResult<&str, Mapping> {
+    map_opt(
+        tuple((
+            terminated(parse_num, space1),
+            terminated(parse_num, space1),
+            parse_num,
+        )),
+        |(dest, src, len)| {
+            src.checked_add(len)?;
+            dest.checked_add(len)?;
+            Some(Mapping { src, dest, len })
+        },
+    )(input)
+}
+
+pub fn parse_num(input: &str) -> IResult<&str, u64> {
+    map_res(digit1, |ds: &str| ds.parse())(input)
+}
+
+pub struct Day5 {
+    seeds: Vec<u64>,
+    maps: Vec<RangeMap>,
+}
+
+impl Solution for Day5 {
+    fn parse(input: &str) -> Self {
+        let (_, (seeds, maps)) = parse_input(input).unwrap();
+
+        Day5 { seeds, maps }
+    }
+
+    fn part_1(&self, _ctx: &Context) -> Option<Answer> {
+        Some(lowest_location(&self.seeds, &self.maps).into())
+    }
+
+    // Part 2 reads the same seed numbers as (start, length) pairs.
+    fn part_2(&self, ctx: &Context) -> Option<Answer> {
+        let ranges: Vec<Range<u64>> = self
+            .seeds
+            .chunks(2)
+            .map(|pair| seed_range(pair[0], *pair.get(1)?))
+            .collect::<Option<_>>()
+            .expect("seeds should be (start, length) pairs that fit in a u64");
+
+        lowest_in_ranges(&ranges, &self.maps, ctx).map(Answer::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day5::{parse_mapping, parse_seeds_2, part_1, part_2, Mapping};
+    use crate::solution::Context;
+    use crate::strategies;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_seed_range_overflow() {
+        assert!(parse_seeds_2("seeds: 18446744073709551615 1").is_err());
+        assert_eq!(
+            vec![u64::MAX - 1..u64::MAX],
+            parse_seeds_2("seeds: 18446744073709551614 1").unwrap().1
+        );
+    }
+
+    #[test]
+    fn test_mapping_overflow() {
+        assert!(parse_mapping("0 18446744073709551615 2").is_err());
+        assert!(parse_mapping("18446744073709551615 0 2").is_err());
+
+        let mapping = parse_mapping("0 18446744073709551614 1").unwrap().1;
+        assert_eq!(Some(0), mapping.mapped(u64::MAX - 1));
+        assert_eq!(None, mapping.mapped(u64::MAX));
+
+        let mapping = Mapping {
+            src: u64::MAX - 1,
+            dest: u64::MAX,
+            len: 5,
+        };
+        assert_eq!(Some(u64::MAX), mapping.mapped(u64::MAX - 1));
+        assert_eq!(None, mapping.mapped(u64::MAX));
+    }
+
+    proptest! {
+        #[test]
+        fn test_ranges_match_listed_seeds(input in strategies::almanac()) {
+            let (seeds, maps) = input.split_once("\n\n").unwrap();
+            let nums: Vec<u64> = seeds[7..].split(' ').map(|n| n.parse().unwrap()).collect();
+
+            let listed: Vec<String> = nums
+                .chunks(2)
+                .flat_map(|pair| pair[0]..pair[0] + pair[1])
+                .map(|seed| seed.to_string())
+                .collect();
+            let expanded = format!("seeds: {}\n\n{maps}", listed.join(" "));
+
+            prop_assert_eq!(Some(part_1(&expanded)), part_2(&input, &Context::default()));
+        }
+    }
+}
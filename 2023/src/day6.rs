@@ -0,0 +1,144 @@
+use crate::solution::{Answer, Context, Solution};
+use nom::bytes::complete::tag;
+use nom::character::complete::{digit1, newline, space1};
+use nom::combinator::{map, map_res};
+use nom::multi::separated_list1;
+use nom::sequence::{pair, preceded, separated_pair};
+use nom::IResult;
+
+pub fn solve(input: &[(u64, u64)]) -> usize {
+    input
+        .iter()
+        .map(|&(time, distance)| ways_to_win(time, distance))
+        .product()
+}
+
+// Holding the button for `a` goes a * (time - a), which rises until half the time
+// and falls the same way after it. The holds that win are a run around the middle,
+// so it's enough to search for where the run starts, however long the race.
+pub fn ways_to_win(time: u64, distance: u64) -> usize {
+    let (time, distance) = (time as u128, distance as u128);
+    let goes = |a: u128| a * (time - a);
+
+    let (mut lo, mut hi) = (0, time / 2);
+    if goes(hi) <= distance {
+        return 0;
+    }
+    while lo < hi {
+        let a = (lo + hi) / 2;
+        if goes(a) > distance {
+            hi = a;
+        } else {
+            lo = a + 1;
+        }
+    }
+
+    (time - 2 * lo + 1) as usize
+}
+
+pub fn parse_races(input: &str) -> IResult<&str, Vec<(u64, u64)>> {
+    map(
+        separated_pair(parse_row("Time:"), newline, parse_row("Distance:")),
+        |(times, distances)| times.into_iter().zip(distances).collect(),
+    )(input)
+}
+
+// Part 2 reads each row as a single number, ignoring the spaces between digits.
+pub fn parse_race(input: &str) -> IResult<&str, (u64, u64)> {
+    separated_pair(parse_kerned("Time:"), newline, parse_kerned("Distance:"))(input)
+}
+
+pub fn parse_row<'a>(label: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<u64>> {
+    preceded(pair(tag(label), space1), separated_list1(space1, parse_num))
+}
+
+pub fn parse_kerned<'a>(label: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str, u64> {
+    preceded(
+        pair(tag(label), space1),
+        map_res(separated_list1(space1, digit1), |ds: Vec<&str>| {
+            ds.concat().parse()
+        }),
+    )
+}
+
+pub fn parse_num(input: &str) -> IResult<&str, u64> {
+    map_res(digit1, |s: &str| s.parse())(input)
+}
+
+pub struct Day6 {
+    races: Vec<(u64, u64)>,
+    race: (u64, u64),
+}
+
+impl Solution for Day6 {
+    fn parse(input: &str) -> Self {
+        let (_, races) = parse_races(input).unwrap();
+        let (_, race) = parse_race(input).unwrap();
+
+        Day6 { races, race }
+    }
+
+    fn part_1(&self, _ctx: &Context) -> Option<Answer> {
+        Some(solve(&self.races).into())
+    }
+
+    fn part_2(&self, _ctx: &Context) -> Option<Answer> {
+        Some(solve(&[self.race]).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day6::{parse_race, parse_races, solve, ways_to_win};
+    use crate::strategies;
+    use indoc::indoc;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_parse() {
+        let input = indoc! {"
+            Time:      7  15   30
+            Distance:  9  40  200
+        "};
+
+        let (_, races) = parse_races(input).unwrap();
+        assert_eq!(vec![(7, 9), (15, 40), (30, 200)], races);
+        assert_eq!(288, solve(&races));
+
+        let (_, race) = parse_race(input).unwrap();
+        assert_eq!((71530, 940200), race);
+    }
+
+    #[test]
+    fn test_long_races() {
+        assert_eq!(0, ways_to_win(0, 0));
+        assert_eq!(0, ways_to_win(4, 4));
+        assert_eq!(1, ways_to_win(4, 3));
+        assert_eq!(u64::MAX as usize - 1, ways_to_win(u64::MAX, 0));
+        assert_eq!(0, ways_to_win(3, 2));
+    }
+
+    // Holding for `a` wins when a^2 - ta + d < 0, i.e. strictly between the roots.
+    fn closed_form(time: u64, distance: u64) -> usize {
+        let disc = (time * time) as f64 - 4.0 * distance as f64;
+        if disc < 0.0 {
+            return 0;
+        }
+
+        let lo = (time as f64 - disc.sqrt()) / 2.0;
+        let hi = (time as f64 + disc.sqrt()) / 2.0;
+        let first = lo.floor() as u64 + 1;
+        let last = hi.ceil() as u64 - 1;
+
+        (first..=last.min(time - 1)).count()
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_closed_form(races in strategies::races()) {
+            let expected: usize = races.iter().map(|&(t, d)| closed_form(t, d)).product();
+
+            prop_assert_eq!(expected, solve(&races));
+        }
+    }
+}
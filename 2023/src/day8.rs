@@ -0,0 +1,439 @@
+use crate::cancel::Cancel;
+use crate::solution::{Answer, Context, Solution};
+use crate::visualize::{Frame, Shade, Visualize};
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::{alphanumeric1, char, multispace1, newline};
+use nom::combinator::{map, value};
+use nom::multi::{many_till, separated_list1};
+use nom::sequence::{delimited, separated_pair};
+use nom::IResult;
+use num::Integer;
+use std::collections::HashMap;
+
+// How many steps to walk between looking at the cancel token, so that checking it
+// doesn't cost more than the walk itself.
+const CHECK_EVERY: usize = 1 << 12;
+
+// Both parts give up on an empty path, since nothing would ever move along it.
+pub fn part_1(path: &Path, graph: &Graph, cancel: &Cancel) -> Option<usize> {
+    if path.is_empty() {
+        return None;
+    }
+    let mut current = &("AAA".to_string());
+
+    for (count, step) in path.iter().cycle().enumerate() {
+        if current == "ZZZ" {
+            return Some(count);
+        }
+        if count % CHECK_EVERY == 0 && cancel.is_cancelled() {
+            return None;
+        }
+
+        let edges = graph.get(current).unwrap();
+        if *step == Direction::Left {
+            current = &edges.0;
+        } else {
+            current = &edges.1;
+        }
+    }
+
+    unreachable!()
+}
+
+pub fn part_2(path: &Path, graph: &Graph, cancel: &Cancel) -> Option<usize> {
+    if path.is_empty() {
+        return None;
+    }
+    let mut currents: Vec<_> = graph
+        .keys()
+        .filter(|g| g.ends_with('A'))
+        .cloned()
+        .map(Some)
+        .collect();
+
+    let mut steps: HashMap<String, usize> = HashMap::new();
+
+    for (count, step) in path.iter().cycle().enumerate() {
+        let mut done = true;
+        for current_opt in currents.iter_mut() {
+            if let Some(current) = current_opt {
+                done = false;
+                if current.ends_with('Z') {
+                    steps.insert(current.clone(), count);
+                    current_opt.take();
+                    continue;
+                }
+                let edges = graph.get(current).unwrap();
+                if *step == Direction::Left {
+                    *current = edges.0.to_string();
+                } else {
+                    *current = edges.1.to_string();
+                }
+            }
+        }
+        if done {
+            break;
+        }
+        if count % CHECK_EVERY == 0 && cancel.is_cancelled() {
+            return None;
+        }
+    }
+
+    Some(steps.values().fold(1, |a, b| a.lcm(b)))
+}
+
+pub type Path = Vec<Direction>;
+pub type Graph = HashMap<String, (String, String)>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Left,
+    Right,
+}
+
+pub fn parse_instructions(input: &str) -> IResult<&str, (Path, Graph)> {
+    separated_pair(parse_path, multispace1, parse_graph)(input)
+}
+
+pub fn parse_path(input: &str) -> IResult<&str, Path> {
+    let (rest, (path, _)) = many_till(parse_direction, newline)(input)?;
+    Ok((rest, path))
+}
+
+pub fn parse_direction(input: &str) -> IResult<&str, Direction> {
+    alt((
+        value(Direction::Left, char('L')),
+        value(Direction::Right, char('R')),
+    ))(input)
+}
+
+pub fn parse_graph(input: &str) -> IResult<&str, Graph> {
+    map(separated_list1(newline, parse_node), |nodes| {
+        nodes
+            .into_iter()
+            .fold(HashMap::new(), |mut acc, (k, (l, r))| {
+                acc.insert(k.to_string(), (l.to_string(), r.to_string()));
+                acc
+            })
+    })(input)
+}
+
+pub fn parse_node(input: &str) -> IResult<&str, (&str, (&str, &str))> {
+    separated_pair(
+        alphanumeric1,
+        tag(" = "),
+        delimited(
+            char('('),
+            separated_pair(alphanumeric1, tag(", "), alphanumeric1),
+            char(')'),
+        ),
+    )(input)
+}
+
+// Moves every ghost of part 2 a step per frame until each has been on a node
+// ending in Z, which is when their cycles can be lined up.
+pub struct GhostWalk {
+    path: Path,
+    nodes: Vec<String>,
+    // The nodes each node leads to, left and right, by index into `nodes`.
+    edges: Vec<(usize, usize)>,
+    // Where the ghosts are every `CHECKPOINT` steps, so a frame only has to walk
+    // on from the last one before it.
+    checkpoints: Vec<Vec<usize>>,
+    steps: usize,
+    first_z: Vec<Option<usize>>,
+}
+
+const CHECKPOINT: usize = 1 << 10;
+
+impl GhostWalk {
+    pub fn new(input: &str) -> Result<Self, String> {
+        let Ok((_, (path, graph))) = parse_instructions(input) else {
+            return Err("cannot parse the network".to_string());
+        };
+        if path.is_empty() {
+            return Err("the path has no directions, so no ghost can move".to_string());
+        }
+
+        let mut nodes: Vec<String> = graph.keys().cloned().collect();
+        nodes.sort();
+        let index: HashMap<&str, usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(idx, node)| (node.as_str(), idx))
+            .collect();
+        let edges = nodes
+            .iter()
+            .map(|node| {
+                let (left, right) = &graph[node];
+                let lookup = |next: &String| {
+                    index
+                        .get(next.as_str())
+                        .copied()
+                        .ok_or_else(|| format!("{node} leads to {next}, which has no entry"))
+                };
+                Ok((lookup(left)?, lookup(right)?))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let mut current: Vec<usize> = (0..nodes.len())
+            .filter(|&n| nodes[n].ends_with('A'))
+            .collect();
+        if current.is_empty() {
+            return Err("no node ends in A, so there are no ghosts to walk".to_string());
+        }
+        let mut first_z = vec![None; current.len()];
+        let mut checkpoints = Vec::new();
+
+        // Each ghost is in a cycle by the time it has seen every node at every
+        // point of the path, so one that hasn't met a Z by then never will.
+        let mut step = 0;
+        loop {
+            if step % CHECKPOINT == 0 {
+                checkpoints.push(current.clone());
+            }
+            for (ghost, &node) in current.iter().enumerate() {
+                if nodes[node].ends_with('Z') {
+                    first_z[ghost].get_or_insert(step);
+                }
+            }
+            if first_z.iter().all(Option::is_some) || step >= path.len() * nodes.len() {
+                break;
+            }
+
+            advance(&edges, &path[step % path.len()], &mut current);
+            step += 1;
+        }
+
+        Ok(GhostWalk {
+            path,
+            nodes,
+            edges,
+            checkpoints,
+            steps: step + 1,
+            first_z,
+        })
+    }
+
+    // Where the ghosts are after `step` steps.
+    fn positions(&self, step: usize) -> Vec<usize> {
+        let mut current = self.checkpoints[step / CHECKPOINT].clone();
+        for at in step - step % CHECKPOINT..step {
+            advance(&self.edges, &self.path[at % self.path.len()], &mut current);
+        }
+        current
+    }
+}
+
+fn advance(edges: &[(usize, usize)], direction: &Direction, current: &mut [usize]) {
+    for node in current {
+        *node = match direction {
+            Direction::Left => edges[*node].0,
+            Direction::Right => edges[*node].1,
+        };
+    }
+}
+
+impl Visualize for GhostWalk {
+    fn frame_count(&self) -> usize {
+        self.steps
+    }
+
+    fn frame(&self, idx: usize) -> Frame {
+        let current = self.positions(idx);
+        let on_z = current
+            .iter()
+            .filter(|&&n| self.nodes[n].ends_with('Z'))
+            .count();
+
+        let mut caption = format!("step {idx}, {on_z} of {} ghosts on a Z node", current.len());
+        if idx + 1 == self.steps && self.first_z.iter().all(Option::is_some) {
+            let steps = self.first_z.iter().flatten().fold(1, |a, b| a.lcm(b));
+            caption += &format!(", all on one after {steps} steps");
+        }
+
+        let mut frame = Frame::new(caption);
+        let next = idx % self.path.len();
+        for (at, direction) in self.path.iter().enumerate() {
+            let glyph = match direction {
+                Direction::Left => "L",
+                Direction::Right => "R",
+            };
+            frame.push_text(
+                glyph,
+                if at == next {
+                    Shade::Active
+                } else {
+                    Shade::Dim
+                },
+            );
+        }
+        frame.new_row();
+
+        for (ghost, &node) in current.iter().enumerate() {
+            frame.new_row();
+            frame.push_text(
+                &format!("{} -> ", self.nodes[self.checkpoints[0][ghost]]),
+                Shade::Plain,
+            );
+
+            let node = &self.nodes[node];
+            let shade = if node.ends_with('Z') {
+                Shade::Marked
+            } else {
+                Shade::Plain
+            };
+            frame.push_text(node, shade);
+
+            if let Some(step) = self.first_z[ghost].filter(|&step| step <= idx) {
+                frame.push_text(&format!("  first Z at step {step}"), Shade::Filled);
+            }
+        }
+        frame
+    }
+}
+
+pub struct Day8 {
+    path: Path,
+    graph: Graph,
+}
+
+impl Solution for Day8 {
+    fn parse(input: &str) -> Self {
+        let (_, (path, graph)) = parse_instructions(input).unwrap();
+        assert!(!path.is_empty(), "the path has no directions");
+
+        Day8 { path, graph }
+    }
+
+    fn part_1(&self, ctx: &Context) -> Option<Answer> {
+        part_1(&self.path, &self.graph, ctx.cancel).map(Answer::from)
+    }
+
+    fn part_2(&self, ctx: &Context) -> Option<Answer> {
+        part_2(&self.path, &self.graph, ctx.cancel).map(Answer::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cancel::Cancel;
+    use crate::day8::{parse_instructions, part_1, part_2, Direction, GhostWalk, Graph, Path};
+    use crate::strategies;
+    use crate::visualize::Visualize;
+    use indoc::indoc;
+    use proptest::prelude::*;
+
+    const TWO_STEPS: &str = include_str!("../examples/day8/two_steps.txt");
+
+    const GHOSTS: &str = include_str!("../examples/day8/ghosts.txt");
+
+    #[test]
+    fn test_parse() {
+        let (_, (path, graph)) = parse_instructions(TWO_STEPS).unwrap();
+
+        println!("PATH: {path:?}");
+        println!("GRAPH: {graph:?}");
+    }
+
+    #[test]
+    fn test_ghost_walk() {
+        let walk = GhostWalk::new(GHOSTS).unwrap();
+        assert_eq!(4, walk.frame_count());
+
+        let frame = walk.frame(3);
+        assert_eq!(
+            "step 3, 1 of 2 ghosts on a Z node, all on one after 6 steps",
+            frame.caption
+        );
+        assert_eq!(
+            "LR\n\n11A -> 11B  first Z at step 2\n22A -> 22Z  first Z at step 3\n",
+            frame.text()
+        );
+        assert_eq!(Some((1, 0)), frame.focus());
+
+        // A walk longer than the steps between checkpoints.
+        let mut long = "L\n\nAAA = (N1, N1)\n".to_string();
+        for i in 1..1500 {
+            let next = if i == 1499 {
+                "ZZZ".to_string()
+            } else {
+                format!("N{}", i + 1)
+            };
+            long += &format!("N{i} = ({next}, {next})\n");
+        }
+        long += "ZZZ = (ZZZ, ZZZ)\n";
+        let walk = GhostWalk::new(&long).unwrap();
+        assert_eq!(1501, walk.frame_count());
+        assert!(walk.frame(1100).text().ends_with("AAA -> N1100\n"));
+        assert!(walk
+            .frame(1500)
+            .caption
+            .ends_with("all on one after 1500 steps"));
+
+        assert_eq!(
+            Err("no node ends in A, so there are no ghosts to walk".to_string()),
+            GhostWalk::new("L\n\nBBB = (BBB, BBB)\n").map(|_| ())
+        );
+        assert!(GhostWalk::new("L\n\nAAA = (BBB, BBB)\n").is_err());
+        assert_eq!(
+            Err("the path has no directions, so no ghost can move".to_string()),
+            GhostWalk::new("\n\nAAA = (AAA, AAA)\n").map(|_| ())
+        );
+    }
+
+    #[test]
+    fn test_cancelled_walk() {
+        let (_, (path, graph)) = parse_instructions(indoc! {"
+            L
+
+            AAA = (BBB, BBB)
+            BBB = (AAA, AAA)
+            ZZZ = (ZZZ, ZZZ)
+        "})
+        .unwrap();
+
+        let cancel = Cancel::never();
+        cancel.cancel();
+        assert_eq!(None, part_1(&path, &graph, &cancel));
+
+        // Without a path there's nothing to walk, cancelled or not.
+        let (_, (path, graph)) = parse_instructions("\n\nAAA = (AAA, AAA)\n").unwrap();
+        assert!(path.is_empty());
+        assert_eq!(None, part_1(&path, &graph, &Cancel::never()));
+        assert_eq!(None, part_2(&path, &graph, &Cancel::never()));
+    }
+
+    // Moves every ghost at once until they all stand on a `Z` node together.
+    fn lockstep(path: &Path, graph: &Graph) -> usize {
+        let mut currents: Vec<&String> = graph.keys().filter(|k| k.ends_with('A')).collect();
+
+        for (count, step) in path.iter().cycle().enumerate() {
+            if count > 0 && currents.iter().all(|c| c.ends_with('Z')) {
+                return count;
+            }
+            for current in currents.iter_mut() {
+                let edges = &graph[*current];
+                *current = if *step == Direction::Left {
+                    &edges.0
+                } else {
+                    &edges.1
+                };
+            }
+        }
+
+        unreachable!()
+    }
+
+    proptest! {
+        #[test]
+        fn test_lcm_matches_lockstep(input in strategies::network()) {
+            let (_, (path, graph)) = parse_instructions(&input).unwrap();
+
+            let chain = graph.keys().filter(|k| k.starts_with("AA")).count();
+            prop_assert_eq!(Some(chain), part_1(&path, &graph, &Cancel::never()));
+            prop_assert_eq!(Some(lockstep(&path, &graph)), part_2(&path, &graph, &Cancel::never()));
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use crate::solution::{Answer, Context, Solution};
+use crate::{parse_lines, BadLine};
+use std::borrow::Borrow;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+pub fn stream_seqs(
+    lines: impl IntoIterator<Item = impl AsRef<str>>,
+) -> impl Iterator<Item = Result<Seq, BadLine>> {
+    parse_lines(lines, |line| line.parse().ok())
+}
+
+pub fn part_1(seqs: impl IntoIterator<Item = impl Borrow<Seq>>) -> i64 {
+    seqs.into_iter().map(|s| s.borrow().next_num()).sum()
+}
+
+pub fn part_2(seqs: impl IntoIterator<Item = impl Borrow<Seq>>) -> i64 {
+    seqs.into_iter().map(|s| s.borrow().prev_num()).sum()
+}
+
+pub struct Seq(Vec<i64>);
+
+impl Seq {
+    pub fn next_num(&self) -> i64 {
+        if self.0.iter().all(|&v| v == 0) {
+            return 0;
+        }
+
+        let ns = self.next_seq();
+        let next_num = ns.next_num();
+
+        *self.0.last().unwrap() + next_num
+    }
+
+    pub fn prev_num(&self) -> i64 {
+        if self.0.iter().all(|&v| v == 0) {
+            return 0;
+        }
+
+        let ns = self.next_seq();
+        let prev_num = ns.prev_num();
+
+        *self.0.first().unwrap() - prev_num
+    }
+
+    pub fn next_seq(&self) -> Self {
+        let nums: Vec<i64> = self.0.windows(2).map(|ns| ns[1] - ns[0]).collect();
+
+        Seq(nums)
+    }
+}
+
+impl FromStr for Seq {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split_whitespace()
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .map(Seq)
+    }
+}
+
+pub struct Day9 {
+    seqs: Vec<Seq>,
+}
+
+impl Solution for Day9 {
+    fn parse(input: &str) -> Self {
+        Day9 {
+            seqs: stream_seqs(input.lines())
+                .collect::<Result<_, _>>()
+                .unwrap(),
+        }
+    }
+
+    fn part_1(&self, _ctx: &Context) -> Option<Answer> {
+        Some(part_1(&self.seqs).into())
+    }
+
+    fn part_2(&self, _ctx: &Context) -> Option<Answer> {
+        Some(part_2(&self.seqs).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day9::{part_1, part_2, stream_seqs, Seq};
+    use crate::{strategies, stream_lines};
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../examples/day9/example.txt");
+
+    #[test]
+    fn test_streaming() {
+        let rdr = std::io::Cursor::new(EXAMPLE.as_bytes());
+
+        assert_eq!(
+            114,
+            part_1(stream_seqs(stream_lines(rdr).map(Result::unwrap)).map(Result::unwrap))
+        );
+
+        assert!(stream_seqs(["1 2 x"]).next().unwrap().is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn test_extrapolates_polynomials((input, next, prev) in strategies::polynomial_sequences()) {
+            let seqs: Vec<Seq> = input.lines().flat_map(|l| l.parse()).collect();
+
+            prop_assert_eq!(next, part_1(&seqs));
+            prop_assert_eq!(prev, part_2(&seqs));
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use crate::solution::{solution, Context};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// Puzzle examples live in `examples/dayN/<name>.txt`, each next to a `<name>.json`
+// with the answers expected from it. The sidecar holds one set of answers, or a list
+// of them when the same input is checked with different params.
+#[derive(Debug)]
+pub struct Example {
+    pub day: u8,
+    pub path: PathBuf,
+    pub input: String,
+    pub expected: Vec<Expected>,
+}
+
+// A part without an answer isn't checked, since some examples only make sense for
+// one of them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Expected {
+    #[serde(default, deserialize_with = "answer", serialize_with = "write_answer")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_1: Option<String>,
+    #[serde(default, deserialize_with = "answer", serialize_with = "write_answer")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_2: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub params: BTreeMap<String, u64>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Sidecar {
+    Many(Vec<Expected>),
+    One(Expected),
+}
+
+// Answers may be written as JSON numbers or, for ones too big for those, strings.
+fn answer<'de, D: Deserializer<'de>>(de: D) -> Result<Option<String>, D::Error> {
+    Ok(match Value::deserialize(de)? {
+        Value::Null => None,
+        Value::String(s) => Some(s),
+        other => Some(other.to_string()),
+    })
+}
+
+// And written back as numbers where JSON can hold them exactly.
+fn write_answer<S: Serializer>(answer: &Option<String>, ser: S) -> Result<S::Ok, S::Error> {
+    let answer = answer.as_deref().unwrap_or_default();
+    match answer.parse::<i64>() {
+        Ok(n) => ser.serialize_i64(n),
+        Err(_) => ser.serialize_str(answer),
+    }
+}
+
+pub fn examples_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("examples")
+}
+
+// Examples that `fetch` found on a puzzle page wait in here until someone has
+// checked them, added any params they need, and moved them up next to the rest.
+pub fn fetched_dir(root: &Path, day: u8) -> PathBuf {
+    root.join(format!("day{day}/fetched"))
+}
+
+// Every example under `root`, in day and then file name order. Subdirectories,
+// like the one holding fetched examples, are left alone.
+pub fn discover(root: &Path) -> io::Result<Vec<Example>> {
+    let mut examples = Vec::new();
+
+    for entry in fs::read_dir(root)? {
+        let dir = entry?.path();
+        let day = dir
+            .file_name()
+            .and_then(|n| n.to_str()?.strip_prefix("day")?.parse().ok());
+        let Some(day) = day else {
+            continue;
+        };
+
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.is_file() && path.extension().is_some_and(|ext| ext == "txt") {
+                examples.push(load(day, path)?);
+            }
+        }
+    }
+
+    examples.sort_by(|a, b| (a.day, &a.path).cmp(&(b.day, &b.path)));
+    Ok(examples)
+}
+
+fn load(day: u8, path: PathBuf) -> io::Result<Example> {
+    let input = fs::read_to_string(&path)?;
+
+    let sidecar = path.with_extension("json");
+    let invalid = |err: String| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: {err}", sidecar.display()),
+        )
+    };
+    let json = fs::read_to_string(&sidecar).map_err(|err| invalid(err.to_string()))?;
+    let sidecar: Sidecar = serde_json::from_str(&json).map_err(|err| invalid(err.to_string()))?;
+
+    let expected = match sidecar {
+        Sidecar::Many(expected) => expected,
+        Sidecar::One(expected) => vec![expected],
+    };
+    Ok(Example {
+        day,
+        path,
+        input,
+        expected,
+    })
+}
+
+// Writes `<name>.txt` and its sidecar into `dir`.
+pub fn write(dir: &Path, name: &str, input: &str, expected: &Expected) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    fs::write(dir.join(format!("{name}.txt")), input)?;
+
+    let json = serde_json::to_string_pretty(expected).map_err(io::Error::other)?;
+    fs::write(dir.join(format!("{name}.json")), json + "\n")
+}
+
+// Solves the example with each set of params, describing every answer that didn't
+// match.
+pub fn check(example: &Example) -> Vec<String> {
+    let name = example.path.display();
+    let Some(parse) = solution(example.day) else {
+        return vec![format!("{name}: no solution for day {}", example.day)];
+    };
+    let solved = parse(&example.input);
+
+    let mut failures = Vec::new();
+    for expected in &example.expected {
+        let ctx = Context {
+            params: &expected.params,
+            ..Context::default()
+        };
+
+        for (part, want) in [(1, &expected.part_1), (2, &expected.part_2)] {
+            let Some(want) = want else {
+                continue;
+            };
+
+            let got = match part {
+                1 => solved.part_1(&ctx),
+                _ => solved.part_2(&ctx),
+            };
+            let got = got.map(|answer| answer.value);
+            if got.as_ref() != Some(want) {
+                failures.push(format!(
+                    "{name} part {part} {:?}: expected {want}, got {}",
+                    expected.params,
+                    got.unwrap_or_else(|| "nothing".to_string())
+                ));
+            }
+        }
+    }
+    failures
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::examples::{check, discover, examples_dir, fetched_dir, write, Expected, Sidecar};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_sidecar() {
+        let Sidecar::One(one) = serde_json::from_str(r#"{"part_1": 374}"#).unwrap() else {
+            panic!("expected a single set of answers");
+        };
+        assert_eq!(Some("374".to_string()), one.part_1);
+        assert_eq!(None, one.part_2);
+
+        let many = r#"[{"part_2": "82000210"}, {"part_2": 1030, "params": {"factor": 10}}]"#;
+        let Sidecar::Many(many) = serde_json::from_str(many).unwrap() else {
+            panic!("expected a list of answers");
+        };
+        assert_eq!(
+            vec![
+                Expected {
+                    part_2: Some("82000210".to_string()),
+                    ..Expected::default()
+                },
+                Expected {
+                    part_2: Some("1030".to_string()),
+                    params: BTreeMap::from([("factor".to_string(), 10)]),
+                    ..Expected::default()
+                }
+            ],
+            many
+        );
+
+        assert!(serde_json::from_str::<Sidecar>(r#"{"part1": 374}"#).is_err());
+    }
+
+    #[test]
+    fn test_examples() {
+        let examples = discover(&examples_dir()).unwrap();
+        assert!(!examples.is_empty());
+
+        let failures: Vec<String> = examples.iter().flat_map(check).collect();
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+
+    #[test]
+    fn test_write() {
+        let root = std::env::temp_dir().join(format!("aoc2023-examples-{}", std::process::id()));
+        let expected = Expected {
+            part_1: Some("374".to_string()),
+            part_2: Some("-2".to_string()),
+            params: BTreeMap::from([("factor".to_string(), 10)]),
+        };
+        write(&root.join("day11"), "page_1", "#.\n", &expected).unwrap();
+
+        let json = std::fs::read_to_string(root.join("day11/page_1.json")).unwrap();
+        assert!(json.contains(r#""part_1": 374,"#));
+
+        // Fetched examples aren't checked until they're moved out of their directory.
+        write(
+            &fetched_dir(&root, 11),
+            "page_1",
+            "#.\n",
+            &Expected::default(),
+        )
+        .unwrap();
+
+        let examples = discover(&root).unwrap();
+        assert_eq!(1, examples.len());
+        assert_eq!((11, "#.\n"), (examples[0].day, &*examples[0].input));
+        assert_eq!(vec![expected], examples[0].expected);
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,210 @@
+use crate::raster::{render, Palette, Rgb};
+use crate::visualize::Visualize;
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Gif,
+    Apng,
+}
+
+impl Format {
+    // `.gif` for a GIF, `.png` or `.apng` for an APNG.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "gif" => Some(Format::Gif),
+            "png" | "apng" => Some(Format::Apng),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Options {
+    // Side of a grid cell in pixels.
+    pub cell: usize,
+    pub palette: Palette,
+    pub fps: u32,
+    // Keeps every `every`th frame, for walks too long to show each step of.
+    pub every: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            cell: 8,
+            palette: Palette::dark(),
+            fps: 10,
+            every: 1,
+        }
+    }
+}
+
+// Which frames out of `count` an export keeps: every `every`th one, and always
+// the last one since that's where the answer is.
+pub fn picked(count: usize, every: usize) -> Vec<usize> {
+    let mut picked: Vec<usize> = (0..count).step_by(every.max(1)).collect();
+    if count > 0 && picked.last() != Some(&(count - 1)) {
+        picked.push(count - 1);
+    }
+    picked
+}
+
+// Writes the frames as an animation that loops forever, returning how many it
+// wrote. Both formats index their pixels into the palette's colours, so nothing
+// needs quantizing. Nothing is written if a cell would have no pixels.
+pub fn export(
+    viz: &dyn Visualize,
+    format: Format,
+    options: &Options,
+    out: impl Write,
+) -> io::Result<usize> {
+    if options.cell == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "a cell must be at least a pixel",
+        ));
+    }
+    let picked = picked(viz.frame_count(), options.every);
+    if picked.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "no frames"));
+    }
+
+    // The frames are made twice, once to size the canvas and once to draw them,
+    // rather than all being held at once.
+    let (mut cols, mut rows) = (0, 0);
+    for &idx in &picked {
+        let frame = viz.frame(idx);
+        cols = cols.max(frame.width());
+        rows = rows.max(frame.rows.len());
+    }
+
+    let table = options.palette.table();
+    let index: HashMap<Rgb, u8> = table
+        .iter()
+        .enumerate()
+        .map(|(idx, &rgb)| (rgb, idx as u8))
+        .collect();
+    let frames = picked.iter().map(|&idx| {
+        let canvas = render(&viz.frame(idx), cols, rows, options.cell, &options.palette);
+        canvas
+            .data
+            .chunks_exact(3)
+            .map(|rgb| index[rgb])
+            .collect::<Vec<u8>>()
+    });
+
+    let (width, height) = (cols * options.cell, rows * options.cell);
+    let too_big = || {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{width}x{height} is too big, try a smaller cell"),
+        )
+    };
+    let palette: Vec<u8> = table.concat();
+    let fps = options.fps.max(1);
+
+    match format {
+        Format::Gif => {
+            let width = u16::try_from(width).map_err(|_| too_big())?;
+            let height = u16::try_from(height).map_err(|_| too_big())?;
+            let mut encoder =
+                gif::Encoder::new(out, width, height, &palette).map_err(io::Error::other)?;
+            encoder
+                .set_repeat(gif::Repeat::Infinite)
+                .map_err(io::Error::other)?;
+
+            // GIF delays are in hundredths of a second.
+            let delay = (100 / fps).max(1) as u16;
+            for pixels in frames {
+                let mut frame = gif::Frame::from_indexed_pixels(width, height, pixels, None);
+                frame.delay = delay;
+                encoder.write_frame(&frame).map_err(io::Error::other)?;
+            }
+        }
+        Format::Apng => {
+            let width = u32::try_from(width).map_err(|_| too_big())?;
+            let height = u32::try_from(height).map_err(|_| too_big())?;
+            let mut encoder = png::Encoder::new(out, width, height);
+            encoder.set_color(png::ColorType::Indexed);
+            encoder.set_depth(png::BitDepth::Eight);
+            encoder.set_palette(palette);
+            encoder
+                .set_animated(picked.len() as u32, 0)
+                .map_err(io::Error::other)?;
+            encoder
+                .set_frame_delay(1, fps.min(u16::MAX.into()) as u16)
+                .map_err(io::Error::other)?;
+
+            let mut writer = encoder.write_header().map_err(io::Error::other)?;
+            for pixels in frames {
+                writer.write_image_data(&pixels).map_err(io::Error::other)?;
+            }
+            writer.finish().map_err(io::Error::other)?;
+        }
+    }
+
+    Ok(picked.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::export::{export, picked, Format, Options};
+    use crate::visualize::visualizer;
+    use std::path::Path;
+
+    const LOOP: &str = ".....\n.S-7.\n.|.|.\n.L-J.\n.....\n";
+
+    #[test]
+    fn test_picked() {
+        assert_eq!(vec![0, 3, 6, 8], picked(9, 3));
+        assert_eq!(vec![0, 1, 2], picked(3, 1));
+        assert_eq!(Vec::<usize>::new(), picked(0, 2));
+
+        assert_eq!(Some(Format::Gif), Format::from_path(Path::new("loop.gif")));
+        assert_eq!(
+            Some(Format::Apng),
+            Format::from_path(Path::new("loop.apng"))
+        );
+        assert_eq!(None, Format::from_path(Path::new("loop")));
+    }
+
+    #[test]
+    fn test_export() {
+        let viz = visualizer(10, LOOP).unwrap().unwrap();
+        let options = Options {
+            cell: 4,
+            every: 2,
+            ..Options::default()
+        };
+
+        let mut gif = Vec::new();
+        assert_eq!(5, export(&*viz, Format::Gif, &options, &mut gif).unwrap());
+        assert!(gif.starts_with(b"GIF89a"));
+        let mut decoder = gif::DecodeOptions::new().read_info(&gif[..]).unwrap();
+        assert_eq!((20, 20), (decoder.width(), decoder.height()));
+        let mut frames = 0;
+        while decoder.read_next_frame().unwrap().is_some() {
+            frames += 1;
+        }
+        assert_eq!(5, frames);
+
+        let mut apng = Vec::new();
+        export(&*viz, Format::Apng, &options, &mut apng).unwrap();
+        let reader = png::Decoder::new(&apng[..]).read_info().unwrap();
+        let info = reader.info();
+        assert_eq!((20, 20), (info.width, info.height));
+        assert_eq!(Some(5), info.animation_control.map(|a| a.num_frames));
+
+        let mut empty = Vec::new();
+        let options = Options {
+            cell: 0,
+            ..Options::default()
+        };
+        let err = export(&*viz, Format::Gif, &options, &mut empty).unwrap_err();
+        assert_eq!(std::io::ErrorKind::InvalidInput, err.kind());
+        assert!(empty.is_empty());
+    }
+}
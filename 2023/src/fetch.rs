@@ -0,0 +1,285 @@
+use crate::examples::Expected;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+pub fn puzzle_url(day: u8) -> String {
+    format!("https://adventofcode.com/2023/day/{day}")
+}
+
+// Where puzzle pages come from, so that tests can hand over pages of their own.
+pub trait Fetch {
+    fn get(&self, url: &str) -> io::Result<String>;
+}
+
+impl<F: Fetch + ?Sized> Fetch for &F {
+    fn get(&self, url: &str) -> io::Result<String> {
+        (**self).get(url)
+    }
+}
+
+// Fetches over HTTP. With the session cookie of a logged in browser, a page also
+// has part 2 once part 1 has been solved.
+pub struct Http {
+    session: Option<String>,
+}
+
+impl Http {
+    pub fn new(session: Option<String>) -> Self {
+        Http { session }
+    }
+}
+
+impl Fetch for Http {
+    fn get(&self, url: &str) -> io::Result<String> {
+        let mut request = ureq::get(url).set(
+            "User-Agent",
+            "github.com/gshipilov/advent-of-code aoc2023 fetch",
+        );
+        if let Some(session) = &self.session {
+            request = request.set("Cookie", &format!("session={session}"));
+        }
+
+        request.call().map_err(io::Error::other)?.into_string()
+    }
+}
+
+// Keeps every page it fetches in `dir`, so that each is only asked for once.
+pub struct Cached<F> {
+    inner: F,
+    dir: PathBuf,
+}
+
+impl<F: Fetch> Cached<F> {
+    pub fn new(inner: F, dir: PathBuf) -> Self {
+        Cached { inner, dir }
+    }
+
+    fn path(&self, url: &str) -> PathBuf {
+        let name: String = url
+            .trim_start_matches("https://")
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        self.dir.join(name + ".html")
+    }
+
+    // Drops the cached copy of a page, e.g. once part 2 has been unlocked.
+    pub fn forget(&self, url: &str) -> io::Result<()> {
+        match fs::remove_file(self.path(url)) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<F: Fetch> Fetch for Cached<F> {
+    fn get(&self, url: &str) -> io::Result<String> {
+        let path = self.path(url);
+        if let Ok(page) = fs::read_to_string(&path) {
+            return Ok(page);
+        }
+
+        let page = self.inner.get(url)?;
+        fs::create_dir_all(&self.dir)?;
+        fs::write(&path, &page)?;
+        Ok(page)
+    }
+}
+
+// What one part's `<article>` on a puzzle page shows: its `<pre><code>` blocks,
+// and the last answer it emphasizes, which is the one for its example.
+#[derive(Debug, PartialEq, Eq)]
+pub struct PartText {
+    pub blocks: Vec<String>,
+    pub answer: Option<String>,
+}
+
+pub fn parse_page(html: &str) -> Vec<PartText> {
+    sections(html, "<article", "</article>")
+        .map(|article| PartText {
+            blocks: sections(article, "<pre><code>", "</code></pre>")
+                .map(text)
+                .collect(),
+            answer: sections(article, "<code><em>", "</em></code>")
+                .last()
+                .map(text),
+        })
+        .collect()
+}
+
+// Everything between each `start` and the `end` after it.
+fn sections<'a>(html: &'a str, start: &'a str, end: &'a str) -> impl Iterator<Item = &'a str> {
+    html.split(start).skip(1).filter_map(move |rest| {
+        let inside = rest.split_once(end)?.0;
+        // `start` may be an opening tag with attributes still to come.
+        if start.ends_with('>') {
+            Some(inside)
+        } else {
+            Some(inside.split_once('>')?.1)
+        }
+    })
+}
+
+// The text of some HTML with its tags left out and entities decoded.
+fn text(html: &str) -> String {
+    let mut text = String::new();
+    let mut rest = html;
+    while let Some((before, after)) = rest.split_once('<') {
+        text.push_str(before);
+        rest = after.split_once('>').map_or("", |(_, after)| after);
+    }
+    text.push_str(rest);
+
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+// Pairs each part's answer with the block it most likely belongs to: the first one
+// in that part, or part 1's when part 2 goes on with the same example. Blocks that
+// no answer points to are left out.
+pub fn examples_from(parts: &[PartText]) -> Vec<(String, Expected)> {
+    let mut examples: Vec<(String, Expected)> = Vec::new();
+
+    for (idx, part) in parts.iter().take(2).enumerate() {
+        let Some(answer) = &part.answer else {
+            continue;
+        };
+        let Some(block) = part.blocks.first().or(parts[0].blocks.first()) else {
+            continue;
+        };
+
+        let at = match examples.iter().position(|(input, _)| input == block) {
+            Some(at) => at,
+            None => {
+                examples.push((block.clone(), Expected::default()));
+                examples.len() - 1
+            }
+        };
+        let expected = &mut examples[at].1;
+        match idx {
+            0 => expected.part_1 = Some(answer.clone()),
+            _ => expected.part_2 = Some(answer.clone()),
+        }
+    }
+
+    examples
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::examples::Expected;
+    use crate::fetch::{examples_from, parse_page, Cached, Fetch, PartText};
+    use indoc::indoc;
+    use std::cell::Cell;
+    use std::io;
+
+    // Hands back the same page for every URL, counting how often it was asked.
+    struct Canned {
+        page: String,
+        calls: Cell<usize>,
+    }
+
+    impl Fetch for Canned {
+        fn get(&self, _url: &str) -> io::Result<String> {
+            self.calls.set(self.calls.get() + 1);
+            Ok(self.page.clone())
+        }
+    }
+
+    const PAGE: &str = indoc! {r#"
+        <main>
+        <article class="day-desc"><h2>--- Day 0: Test ---</h2>
+        <p>For example:</p>
+        <pre><code>1 &lt; 2
+        <em>3</em> &amp; 4
+        </code></pre>
+        <p>Here the first answer is <code>7</code>, but in total it's <code><em>142</em></code>.</p>
+        <pre><code>not an example</code></pre>
+        </article>
+        <p>Your puzzle answer was <code>54388</code>.</p>
+        <article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+        <p>With the same example, the sum is now <code><em>281</em></code>.</p>
+        </article>
+        </main>
+    "#};
+
+    #[test]
+    fn test_parse_page() {
+        assert_eq!(
+            vec![
+                PartText {
+                    blocks: vec!["1 < 2\n3 & 4\n".to_string(), "not an example".to_string()],
+                    answer: Some("142".to_string()),
+                },
+                PartText {
+                    blocks: vec![],
+                    answer: Some("281".to_string()),
+                },
+            ],
+            parse_page(PAGE)
+        );
+    }
+
+    #[test]
+    fn test_examples_from() {
+        let examples = examples_from(&parse_page(PAGE));
+        assert_eq!(
+            vec![(
+                "1 < 2\n3 & 4\n".to_string(),
+                Expected {
+                    part_1: Some("142".to_string()),
+                    part_2: Some("281".to_string()),
+                    ..Expected::default()
+                }
+            )],
+            examples
+        );
+
+        // Part 2 brings its own example, and part 1 isn't solved yet.
+        let parts = [
+            PartText {
+                blocks: vec!["a".to_string()],
+                answer: None,
+            },
+            PartText {
+                blocks: vec!["b".to_string()],
+                answer: Some("2".to_string()),
+            },
+        ];
+        assert_eq!(
+            vec![(
+                "b".to_string(),
+                Expected {
+                    part_2: Some("2".to_string()),
+                    ..Expected::default()
+                }
+            )],
+            examples_from(&parts)
+        );
+    }
+
+    #[test]
+    fn test_cached() {
+        let dir = std::env::temp_dir().join(format!("aoc2023-fetch-{}", std::process::id()));
+        let canned = Canned {
+            page: PAGE.to_string(),
+            calls: Cell::new(0),
+        };
+        let cached = Cached::new(&canned, dir.clone());
+
+        let url = "https://adventofcode.com/2023/day/0";
+        assert_eq!(PAGE, cached.get(url).unwrap());
+        assert_eq!(PAGE, cached.get(url).unwrap());
+        assert_eq!(1, canned.calls.get());
+
+        cached.forget(url).unwrap();
+        cached.get(url).unwrap();
+        assert_eq!(2, canned.calls.get());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,509 @@
+use crate::progress::Progress;
+use crate::shapes::{
+    almanac_text, card_line, draw_loop, game_line, histogram_outline, with_digit, COLORS,
+    DIGIT_WORDS,
+};
+use crate::solution::{solution, Answer, Context};
+use rand::seq::{IndexedRandom, SliceRandom};
+use rand::{Rng, RngExt};
+use std::collections::HashSet;
+
+// Knobs for the synthetic inputs. `size` is the number of lines or items for most
+// days and the side of the grid for days 3 and 11; the rest only affect one day.
+#[derive(Debug, Clone)]
+pub struct Params {
+    pub size: usize,
+    pub seed_ranges: usize,
+    pub range_len: u64,
+    pub loop_len: usize,
+    pub density: f64,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            size: 100,
+            seed_ranges: 10,
+            range_len: 1000,
+            loop_len: 1000,
+            density: 0.05,
+        }
+    }
+}
+
+// The largest `size` a day's generator goes up to, past which it keeps its answers
+// in range by making no more than this.
+pub fn size_limit(day: u8) -> Option<usize> {
+    match day {
+        // Part 2 joins the race times and distances into one u64.
+        6 => Some(4),
+        // Part 2 is the lcm of chain lengths that grow with the size.
+        8 => Some(1000),
+        _ => None,
+    }
+}
+
+pub fn generate(day: u8, params: &Params, rng: &mut impl Rng) -> Result<String, String> {
+    let size = params.size.clamp(1, size_limit(day).unwrap_or(usize::MAX));
+
+    let input = match day {
+        1 => calibration(rng, size),
+        2 => games(rng, size),
+        3 => schematic(rng, size),
+        4 => scratchcards(rng, size),
+        5 => almanac(
+            rng,
+            size,
+            params.seed_ranges.max(1),
+            params.range_len.max(1),
+        )?,
+        6 => races(rng, size),
+        7 => hands(rng, size),
+        8 => network(rng, size),
+        9 => sequences(rng, size),
+        10 => pipe_loop(rng, params.loop_len),
+        11 => image(rng, size, params.density),
+        _ => return Err(format!("no generator for day {day}")),
+    };
+
+    Ok(input)
+}
+
+// Answers to both parts of a generated input, from the day's own solution.
+pub fn reference_answers(
+    day: u8,
+    input: &str,
+    progress: &dyn Progress,
+) -> Option<(Answer, Answer)> {
+    let solution = solution(day)?(input);
+    let ctx = Context {
+        progress,
+        ..Context::default()
+    };
+
+    Some((solution.part_1(&ctx)?, solution.part_2(&ctx)?))
+}
+
+fn calibration(rng: &mut impl Rng, size: usize) -> String {
+    let mut out = String::new();
+
+    for _ in 0..size {
+        let tokens: Vec<String> = (0..rng.random_range(0..8))
+            .map(|_| match rng.random_range(0..3) {
+                0 => DIGIT_WORDS.choose(rng).unwrap().to_string(),
+                1 => rng.random_range(1..10).to_string(),
+                _ => (0..rng.random_range(1..5))
+                    .map(|_| rng.random_range(b'a'..=b'z') as char)
+                    .collect(),
+            })
+            .collect();
+        let at = rng.random_range(0..=tokens.len());
+
+        out.push_str(&with_digit(tokens, at, rng.random_range(1..10)));
+        out.push('\n');
+    }
+
+    out
+}
+
+fn games(rng: &mut impl Rng, size: usize) -> String {
+    let mut out = String::new();
+
+    for id in 1..=size {
+        let rounds: Vec<Vec<(u32, &str)>> = (0..rng.random_range(1..=6))
+            .map(|_| {
+                let mut colors = COLORS;
+                colors.shuffle(rng);
+                colors[..rng.random_range(1..=3)]
+                    .iter()
+                    .map(|&color| (pick_count(rng), color))
+                    .collect()
+            })
+            .collect();
+
+        out.push_str(&game_line(id, &rounds));
+    }
+
+    out
+}
+
+// Mostly counts that fit in the standard bag, so part 1 has games to add up.
+fn pick_count(rng: &mut impl Rng) -> u32 {
+    if rng.random_bool(0.05) {
+        rng.random_range(13..=20)
+    } else {
+        rng.random_range(1..=12)
+    }
+}
+
+const SYMBOLS: [char; 10] = ['*', '#', '+', '$', '/', '@', '%', '=', '&', '-'];
+
+// Numbers are always followed by a `.` so neighbouring ones never merge.
+fn schematic(rng: &mut impl Rng, size: usize) -> String {
+    let mut out = String::new();
+
+    for _ in 0..size {
+        let mut line = String::new();
+        while line.len() < size {
+            match rng.random_range(0..10) {
+                0 | 1 if size - line.len() >= 4 => {
+                    line.push_str(&rng.random_range(1..1000).to_string());
+                    line.push('.');
+                }
+                2 => line.push(*SYMBOLS.choose(rng).unwrap()),
+                _ => line.push('.'),
+            }
+        }
+        out.push_str(&line);
+        out.push('\n');
+    }
+
+    out
+}
+
+// Ten winning numbers and twenty-five on each card, as in the real input. Cards
+// never win copies of cards past the end of the table.
+fn scratchcards(rng: &mut impl Rng, size: usize) -> String {
+    let mut out = String::new();
+    let mut pool: Vec<u32> = (1..100).collect();
+
+    for id in 1..=size {
+        pool.shuffle(rng);
+        let matches = rng.random_range(0..=10.min(size - id));
+        let (winning, rest) = pool.split_at(10);
+        let mut have: Vec<u32> = winning[..matches].to_vec();
+        have.extend(&rest[..25 - matches]);
+        have.shuffle(rng);
+
+        out.push_str(&card_line(id, winning, &have));
+    }
+
+    out
+}
+
+// `size` mappings per category, each with a disjoint source range.
+// Seeds and mappings fall within a universe four times the total length of the
+// seed ranges. It has to fit in half a u64, so that a range starting anywhere in
+// it still ends inside a u64.
+fn almanac(
+    rng: &mut impl Rng,
+    size: usize,
+    seed_ranges: usize,
+    range_len: u64,
+) -> Result<String, String> {
+    let universe = range_len
+        .checked_mul(seed_ranges as u64)
+        .and_then(|len| len.checked_mul(4))
+        .map(|universe| universe.max((size as u64).saturating_mul(4)))
+        .filter(|&universe| universe <= u64::MAX / 2)
+        .ok_or_else(|| {
+            format!(
+                "{seed_ranges} seed ranges of up to {range_len} or {size} mappings are too many"
+            )
+        })?;
+
+    let seeds: Vec<(u64, u64)> = (0..seed_ranges)
+        .map(|_| {
+            let start = rng.random_range(0..universe);
+            (start, rng.random_range(1..=range_len))
+        })
+        .collect();
+
+    let maps: Vec<Vec<(u64, u64, u64)>> = (0..7)
+        .map(|_| {
+            let mut cuts: Vec<u64> = (0..2 * size)
+                .map(|_| rng.random_range(0..universe))
+                .collect::<HashSet<_>>()
+                .into_iter()
+                .collect();
+            cuts.sort_unstable();
+            cuts.chunks_exact(2)
+                .map(|bounds| {
+                    let dest = rng.random_range(0..universe);
+                    (dest, bounds[0], bounds[1] - bounds[0])
+                })
+                .collect()
+        })
+        .collect();
+
+    Ok(almanac_text(&seeds, &maps))
+}
+
+// Times stay below 100 so part 2's joined numbers fit in a u64 for up to four
+// races, which is as many as the real input has.
+fn races(rng: &mut impl Rng, size: usize) -> String {
+    let races: Vec<(u64, u64)> = (0..size)
+        .map(|_| {
+            let time = rng.random_range(7..100u64);
+            (time, rng.random_range(time * time / 8..time * time / 4))
+        })
+        .collect();
+
+    let row = |label: &str, nums: Vec<u64>| {
+        let cols: Vec<String> = nums.iter().map(|n| format!("{n:>5}")).collect();
+        format!("{label:<9}{}\n", cols.concat())
+    };
+
+    row("Time:", races.iter().map(|r| r.0).collect())
+        + &row("Distance:", races.iter().map(|r| r.1).collect())
+}
+
+const CARDS: [char; 13] = [
+    '2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K', 'A',
+];
+
+fn hands(rng: &mut impl Rng, size: usize) -> String {
+    let mut out = String::new();
+
+    for _ in 0..size {
+        let cards: String = (0..5).map(|_| *CARDS.choose(rng).unwrap()).collect();
+        out.push_str(&format!("{cards} {}\n", rng.random_range(1..=1000)));
+    }
+
+    out
+}
+
+const NODE_CHARS: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const PERIODS: [usize; 6] = [2, 3, 5, 7, 11, 13];
+
+// Six ghosts, the first starting at `AAA`, each walking down its own chain to a
+// `..Z` node that loops back to the second node of the chain. Chain lengths are
+// `size` times distinct small primes, so every ghost reaches `Z` exactly at
+// multiples of its chain length and part 2 stays well within a u64.
+fn network(rng: &mut impl Rng, size: usize) -> String {
+    let path: String = (0..size)
+        .map(|_| if rng.random_bool(0.5) { 'L' } else { 'R' })
+        .collect();
+
+    let mut taken: HashSet<String> = HashSet::from(["AAA".to_string(), "ZZZ".to_string()]);
+    let mut periods = PERIODS;
+    periods.shuffle(rng);
+
+    let mut lines = Vec::new();
+    for (ghost, period) in periods.iter().enumerate() {
+        let len = size * period;
+        let chain: Vec<String> = (0..=len)
+            .map(|i| match (ghost, i) {
+                (0, 0) => "AAA".to_string(),
+                (0, i) if i == len => "ZZZ".to_string(),
+                (_, 0) => node_name(rng, &mut taken, Some(b'A')),
+                (_, i) if i == len => node_name(rng, &mut taken, Some(b'Z')),
+                _ => node_name(rng, &mut taken, None),
+            })
+            .collect();
+
+        for (i, node) in chain.iter().enumerate() {
+            let next = &chain[if i == len { 1 } else { i + 1 }];
+            lines.push(format!("{node} = ({next}, {next})\n"));
+        }
+    }
+    lines.shuffle(rng);
+
+    format!("{path}\n\n{}", lines.concat())
+}
+
+// An unused node name ending in `last`, or in neither `A` nor `Z` if not given.
+fn node_name(rng: &mut impl Rng, taken: &mut HashSet<String>, last: Option<u8>) -> String {
+    loop {
+        let mut name: Vec<u8> = (0..3).map(|_| *NODE_CHARS.choose(rng).unwrap()).collect();
+        match last {
+            Some(c) => name[2] = c,
+            None if matches!(name[2], b'A' | b'Z') => continue,
+            None => {}
+        }
+
+        let name = String::from_utf8(name).unwrap();
+        if taken.insert(name.clone()) {
+            return name;
+        }
+    }
+}
+
+// Twenty-one values of a random polynomial of degree at most five per line.
+fn sequences(rng: &mut impl Rng, size: usize) -> String {
+    let mut out = String::new();
+
+    for _ in 0..size {
+        let coeffs: Vec<i64> = (0..rng.random_range(1..=6))
+            .map(|_| rng.random_range(-3..=3))
+            .collect();
+        let nums: Vec<String> = (0..21i64)
+            .map(|x| {
+                coeffs
+                    .iter()
+                    .rev()
+                    .fold(0, |acc, c| acc * x + c)
+                    .to_string()
+            })
+            .collect();
+
+        out.push_str(&nums.join(" "));
+        out.push('\n');
+    }
+
+    out
+}
+
+// A histogram outline at double scale, grown column by column until the loop is at
+// least `loop_len` tiles long, with stray pipes scattered around it.
+fn pipe_loop(rng: &mut impl Rng, loop_len: usize) -> String {
+    let max_height = (loop_len.isqrt() / 4).max(1);
+
+    // The outline is 4 tiles per column plus twice the height of both ends and of
+    // every step between neighbouring columns.
+    let mut heights = vec![rng.random_range(1..=max_height)];
+    let mut steps = 0;
+    while 4 * heights.len() + 2 * (heights[0] + heights[heights.len() - 1] + steps) < loop_len {
+        let height = rng.random_range(1..=max_height);
+        steps += height.abs_diff(heights[heights.len() - 1]);
+        heights.push(height);
+    }
+
+    let path = histogram_outline(&heights, max_height);
+    let (width, height) = (2 * heights.len() + 3, 2 * max_height + 3);
+
+    let junk = ['.', '.', '.', '|', '-', 'L', 'J', '7', 'F'];
+    let mut grid: Vec<Vec<char>> = (0..height)
+        .map(|_| (0..width).map(|_| *junk.choose(rng).unwrap()).collect())
+        .collect();
+    draw_loop(&mut grid, &path, rng.random_range(0..path.len()));
+
+    grid.iter()
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect()
+}
+
+fn image(rng: &mut impl Rng, size: usize, density: f64) -> String {
+    let mut out = String::new();
+
+    for _ in 0..size {
+        out.extend((0..size).map(|_| {
+            if rng.random_bool(density.clamp(0.0, 1.0)) {
+                '#'
+            } else {
+                '.'
+            }
+        }));
+        out.push('\n');
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generate::{generate, reference_answers, size_limit, Params};
+    use crate::progress::Silent;
+    use crate::solution::{solution, Context};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test_every_day_parses() {
+        let params = Params {
+            size: 30,
+            seed_ranges: 3,
+            range_len: 50,
+            loop_len: 200,
+            density: 0.1,
+        };
+
+        for day in 1..=11 {
+            let mut rng = ChaCha8Rng::seed_from_u64(day as u64);
+            let input = generate(day, &params, &mut rng).unwrap();
+            assert!(reference_answers(day, &input, &Silent).is_some());
+        }
+        assert!(generate(12, &params, &mut ChaCha8Rng::seed_from_u64(0)).is_err());
+    }
+
+    #[test]
+    fn test_seeded() {
+        let params = Params::default();
+        let mut a = ChaCha8Rng::seed_from_u64(7);
+        let mut b = ChaCha8Rng::seed_from_u64(7);
+
+        for day in 1..=11 {
+            assert_eq!(
+                generate(day, &params, &mut a),
+                generate(day, &params, &mut b)
+            );
+        }
+    }
+
+    #[test]
+    fn test_knobs() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+        let params = Params {
+            loop_len: 500,
+            ..Params::default()
+        };
+        let input = generate(10, &params, &mut rng).unwrap();
+        let (part_1, _) = reference_answers(10, &input, &Silent).unwrap();
+        assert!(part_1.value.parse::<usize>().unwrap() * 2 >= 500);
+
+        let params = Params {
+            seed_ranges: 4,
+            ..Params::default()
+        };
+        let input = generate(5, &params, &mut rng).unwrap();
+        let seeds = input.lines().next().unwrap();
+        assert_eq!(8, seeds.split(' ').count() - 1);
+
+        let params = Params {
+            size: 20,
+            density: 1.0,
+            ..Params::default()
+        };
+        let input = generate(11, &params, &mut rng).unwrap();
+        assert_eq!(400, input.matches('#').count());
+    }
+
+    #[test]
+    fn test_limits() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+        let params = Params {
+            seed_ranges: 5,
+            range_len: u64::MAX / 8,
+            ..Params::default()
+        };
+        assert!(generate(5, &params, &mut rng).is_err());
+
+        // As large as the ranges can be, every one of them still parses.
+        let params = Params {
+            seed_ranges: 1,
+            range_len: u64::MAX / 8,
+            ..Params::default()
+        };
+        let input = generate(5, &params, &mut rng).unwrap();
+        let day5 = solution(5).unwrap()(&input);
+        assert!(day5.part_1(&Context::default()).is_some());
+        let params = Params {
+            seed_ranges: 2,
+            ..params
+        };
+        assert!(generate(5, &params, &mut rng).is_err());
+
+        let params = Params {
+            size: 10,
+            ..Params::default()
+        };
+        let input = generate(6, &params, &mut rng).unwrap();
+        assert_eq!(Some(4), size_limit(6));
+        assert_eq!(5, input.lines().next().unwrap().split_whitespace().count());
+    }
+
+    #[test]
+    fn test_known_network_answers() {
+        let params = Params {
+            size: 3,
+            ..Params::default()
+        };
+        let input = generate(8, &params, &mut ChaCha8Rng::seed_from_u64(1)).unwrap();
+        let (part_1, part_2) = reference_answers(8, &input, &Silent).unwrap();
+
+        assert_eq!(0, part_1.value.parse::<usize>().unwrap() % 3);
+        assert_eq!((3 * 2 * 3 * 5 * 7 * 11 * 13).to_string(), part_2.value);
+    }
+}
@@ -1,17 +1,103 @@
+pub mod alloc;
+pub mod cancel;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod examples;
+pub mod export;
+pub mod fetch;
+pub mod generate;
+pub mod parallel;
+pub mod progress;
+pub mod raster;
+pub mod runlog;
+pub mod runner;
+pub mod scaffold;
+pub mod shapes;
+pub mod solution;
+#[cfg(any(test, feature = "proptest"))]
+pub mod strategies;
+pub mod tui;
+pub mod visualize;
+
+use std::fmt;
 use std::fs::File;
-use std::io::{BufRead, BufReader, Read};
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::PathBuf;
 
-pub fn read_input_lines(day: u8) -> Vec<String> {
-    let file = File::open(format!("inputs/day{day}.txt")).unwrap();
-    let rdr = BufReader::new(file);
+// The real puzzle input lives in `inputs/dayN.txt`, any other variant of it in
+// `inputs/dayN-<variant>.txt`.
+pub fn input_path(day: u8, variant: &str) -> PathBuf {
+    match variant {
+        "real" => format!("inputs/day{day}.txt").into(),
+        _ => format!("inputs/day{day}-{variant}.txt").into(),
+    }
+}
+
+pub fn read_input_variant(day: u8, variant: &str) -> io::Result<String> {
+    std::fs::read_to_string(input_path(day, variant))
+}
+
+pub fn input_reader(day: u8) -> BufReader<File> {
+    let file = File::open(input_path(day, "real")).unwrap();
+
+    BufReader::new(file)
+}
+
+// Lines one at a time, each an error if it couldn't be read.
+pub fn stream_lines<R: BufRead>(rdr: R) -> impl Iterator<Item = io::Result<String>> {
+    rdr.lines()
+}
 
-    rdr.lines().map(|l| l.unwrap()).collect()
+pub fn stream_input_lines(day: u8) -> impl Iterator<Item = io::Result<String>> {
+    stream_lines(input_reader(day))
+}
+
+// A line that a streaming parser couldn't read, numbered from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BadLine {
+    pub number: usize,
+    pub line: String,
+}
+
+impl fmt::Display for BadLine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cannot parse line {}: {:?}", self.number, self.line)
+    }
+}
+
+impl std::error::Error for BadLine {}
+
+// Parses lines one at a time, so that a bad one is reported without ending the
+// stream.
+pub fn parse_lines<T>(
+    lines: impl IntoIterator<Item = impl AsRef<str>>,
+    parse: impl Fn(&str) -> Option<T>,
+) -> impl Iterator<Item = Result<T, BadLine>> {
+    lines.into_iter().enumerate().map(move |(idx, line)| {
+        let line = line.as_ref();
+        parse(line).ok_or_else(|| BadLine {
+            number: idx + 1,
+            line: line.to_string(),
+        })
+    })
+}
+
+pub fn read_input_lines(day: u8) -> Vec<String> {
+    stream_input_lines(day).collect::<io::Result<_>>().unwrap()
 }
 
 pub fn read_input(day: u8) -> String {
-    let mut file = File::open(format!("inputs/day{day}.txt")).unwrap();
     let mut buf = String::new();
-    file.read_to_string(&mut buf).unwrap();
+    input_reader(day).read_to_string(&mut buf).unwrap();
 
     buf
 }
@@ -0,0 +1,621 @@
+use aoc2023::alloc::{self, AllocStats, Counting};
+use aoc2023::examples::{self, examples_dir, fetched_dir};
+use aoc2023::export::{export, Format as Animation, Options};
+use aoc2023::fetch::{examples_from, parse_page, puzzle_url, Cached, Fetch, Http};
+use aoc2023::generate::{generate, reference_answers, size_limit, Params};
+use aoc2023::parallel;
+use aoc2023::progress::{Bars, Log, Progress, Silent};
+use aoc2023::raster::Palette;
+use aoc2023::runlog::{self, diff, LoggedPart, Run};
+use aoc2023::runner::{run_days, PartReport, Status};
+use aoc2023::scaffold::new_day;
+use aoc2023::solution::DAYS;
+use aoc2023::tui;
+use aoc2023::visualize::visualizer;
+use aoc2023::{input_path, read_input_variant};
+use clap::builder::RangedU64ValueParser;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use indicatif::{HumanBytes, HumanCount};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::fs::File;
+use std::io::{BufWriter, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+// Only counts once `run --allocs` turns it on.
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+#[derive(Parser)]
+#[command(about = "Advent of Code 2023 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve one or more days
+    Run(RunArgs),
+    /// Generate a synthetic puzzle input for a day
+    Gen(GenArgs),
+    /// Start a new day from a template and register it with the runner
+    New(NewArgs),
+    /// Save the examples and answers from a day's puzzle page under examples/ for
+    /// review
+    Fetch(FetchArgs),
+    /// List the runs in the run log
+    Log,
+    /// Compare the answers and timings of two runs from the run log
+    Diff(DiffArgs),
+    /// Step through how a day gets to its answer, for days 3, 8, 10 and 11
+    Viz(VizArgs),
+}
+
+// Every run is appended here, relative to where it's run like the inputs are.
+const RUN_LOG: &str = "runs.jsonl";
+
+#[derive(Args)]
+struct RunArgs {
+    /// Day to run; may be repeated
+    #[arg(long, required_unless_present = "all")]
+    day: Vec<u8>,
+    /// Run every day
+    #[arg(long, conflicts_with = "day")]
+    all: bool,
+    /// `real` for inputs/dayN.txt, anything else for inputs/dayN-<input>.txt
+    #[arg(long, default_value = "real")]
+    input: String,
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+    #[arg(long, value_enum, default_value_t = ProgressKind::Auto)]
+    progress: ProgressKind,
+    /// Threads to share between days, parts and the work within them; one per CPU
+    /// by default
+    #[arg(long, short)]
+    jobs: Option<usize>,
+    /// Count heap allocations while parsing and solving each part. Runs everything
+    /// on a single thread so that the counts don't mix
+    #[arg(long, conflicts_with = "jobs")]
+    allocs: bool,
+    /// Seconds each day may run before it is stopped and reported as timed out
+    #[arg(long, value_name = "SECONDS", value_parser = parse_seconds)]
+    timeout: Option<Duration>,
+    /// Don't add this run to the run log
+    #[arg(long)]
+    no_log: bool,
+}
+
+fn parse_seconds(arg: &str) -> Result<Duration, String> {
+    let seconds: f64 = arg.parse().map_err(|err| format!("{err}"))?;
+    Duration::try_from_secs_f64(seconds).map_err(|err| format!("{err}"))
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Text,
+    /// A single JSON array of every part
+    Json,
+    /// One JSON object per part and line
+    Ndjson,
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum ProgressKind {
+    /// Silent for JSON output, bars on a terminal and log lines otherwise
+    Auto,
+    Bars,
+    Log,
+    Silent,
+}
+
+impl ProgressKind {
+    fn backend(self, machine_readable: bool) -> Arc<dyn Progress> {
+        match self {
+            ProgressKind::Auto if machine_readable => Arc::new(Silent),
+            ProgressKind::Auto if std::io::stderr().is_terminal() => Arc::new(Bars::new()),
+            ProgressKind::Auto | ProgressKind::Log => Arc::new(Log),
+            ProgressKind::Bars => Arc::new(Bars::new()),
+            ProgressKind::Silent => Arc::new(Silent),
+        }
+    }
+}
+
+#[derive(Args)]
+struct GenArgs {
+    #[arg(long)]
+    day: u8,
+    /// Number of lines or items, or the side of the grid for days 3 and 11; day 6
+    /// makes at most 4 races and day 8 at most 1000 steps of path
+    #[arg(long, default_value_t = Params::default().size)]
+    size: usize,
+    /// RNG seed; the same seed always produces the same input
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+    /// Day 5: number of seed ranges
+    #[arg(long, default_value_t = Params::default().seed_ranges)]
+    seed_ranges: usize,
+    /// Day 5: longest seed range
+    #[arg(long, default_value_t = Params::default().range_len)]
+    range_len: u64,
+    /// Day 10: minimum length of the loop
+    #[arg(long, default_value_t = Params::default().loop_len)]
+    loop_len: usize,
+    /// Day 11: chance of each cell holding a galaxy
+    #[arg(long, default_value_t = Params::default().density)]
+    density: f64,
+    /// Solve the generated input and print both answers to stderr
+    #[arg(long)]
+    answer: bool,
+    #[arg(long, value_enum, default_value_t = ProgressKind::Auto)]
+    progress: ProgressKind,
+    /// Write the input to a file instead of stdout
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct NewArgs {
+    #[arg(long)]
+    day: u8,
+}
+
+#[derive(Args)]
+struct FetchArgs {
+    #[arg(long)]
+    day: u8,
+    /// Fetch the page again rather than use the cached copy, e.g. once part 2 is
+    /// unlocked. Part 2 is only on the page with AOC_SESSION set to a session cookie
+    #[arg(long)]
+    refresh: bool,
+}
+
+#[derive(Args)]
+struct DiffArgs {
+    /// Number of the earlier run, as listed by `log`; the second to last by default
+    before: Option<usize>,
+    /// Number of the later run; the last by default
+    after: Option<usize>,
+}
+
+#[derive(Args)]
+struct VizArgs {
+    #[arg(long)]
+    day: u8,
+    /// `real` for inputs/dayN.txt, anything else for inputs/dayN-<input>.txt
+    #[arg(long, default_value = "real")]
+    input: String,
+    /// Read the input from this file instead, e.g. one of the examples
+    #[arg(long, conflicts_with = "input")]
+    file: Option<PathBuf>,
+    /// Frames per second while playing or in the animation
+    #[arg(long, default_value_t = Options::default().fps)]
+    fps: u32,
+    /// Write the frames to an animated .gif or .png (APNG) instead of showing them
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+    /// Side of a grid cell in the animation, in pixels
+    #[arg(
+        long,
+        requires = "output",
+        default_value_t = Options::default().cell,
+        value_parser = RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    cell: usize,
+    /// Keep only every Nth frame of the animation, and the last one
+    #[arg(long, requires = "output", default_value_t = Options::default().every)]
+    every: usize,
+    #[arg(long, requires = "output", value_enum, default_value_t = PaletteName::Dark)]
+    palette: PaletteName,
+    /// Override a colour of the palette: `background=#rrggbb`, or
+    /// `<dim|plain|marked|active|filled>=<ink>[/<fill>]`; may be repeated
+    #[arg(long, requires = "output", value_name = "NAME=COLOUR")]
+    colour: Vec<String>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum PaletteName {
+    Dark,
+    Light,
+}
+
+fn main() {
+    match Cli::parse().command {
+        Command::Run(args) => run(args),
+        Command::Gen(args) => gen(args),
+        Command::New(args) => new(args),
+        Command::Fetch(args) => fetch(args),
+        Command::Log => log(),
+        Command::Diff(args) => diff_runs(args),
+        Command::Viz(args) => viz(args),
+    }
+}
+
+fn run(args: RunArgs) {
+    let days: Vec<u8> = if args.all {
+        DAYS.iter().map(|&(day, _)| day).collect()
+    } else {
+        args.day
+    };
+
+    if args.allocs {
+        alloc::enable();
+        parallel::init(1);
+    } else if let Some(jobs) = args.jobs {
+        parallel::init(jobs);
+    }
+
+    let started = SystemTime::now();
+    let timeout = args.timeout;
+    let progress = args.progress.backend(!matches!(args.format, Format::Text));
+
+    let mut reports = Vec::new();
+    let (mut finished, mut timed_out, mut failed) = (Vec::new(), Vec::new(), Vec::new());
+    for day_reports in run_days(&days, &args.input, progress, timeout) {
+        let day = day_reports[0].day;
+        if let Some(err) = &day_reports[0].error {
+            eprintln!("Day {day}: {err}");
+        }
+
+        match args.format {
+            Format::Text => print_text(&day_reports),
+            Format::Ndjson => {
+                for report in &day_reports {
+                    println!("{}", serde_json::to_string(report).unwrap());
+                }
+            }
+            Format::Json => {}
+        }
+
+        if day_reports.iter().any(|r| r.status == Status::Failed) {
+            failed.push(day);
+        } else if day_reports.iter().all(|r| r.status == Status::Finished) {
+            finished.push(day);
+        } else {
+            timed_out.push(day);
+        }
+        reports.extend(day_reports);
+    }
+
+    if let Format::Json = args.format {
+        println!("{}", serde_json::to_string_pretty(&reports).unwrap());
+    }
+
+    if !args.no_log {
+        let run = Run::new(started, &args.input, &reports);
+        if let Err(err) = runlog::append(Path::new(RUN_LOG), &run) {
+            eprintln!("Cannot add to {RUN_LOG}: {err}");
+        }
+    }
+
+    if timeout.is_some() {
+        eprintln!("Finished: {}", list_days(&finished));
+        eprintln!("Timed out: {}", list_days(&timed_out));
+    }
+    if !failed.is_empty() {
+        eprintln!("Failed: {}", list_days(&failed));
+    }
+    // Days that never stopped may still hold pool threads, which exiting cleans up.
+    if !timed_out.is_empty() || !failed.is_empty() {
+        std::process::exit(1);
+    }
+}
+
+fn list_days(days: &[u8]) -> String {
+    if days.is_empty() {
+        return "none".to_string();
+    }
+
+    days.iter()
+        .map(|day| day.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn print_text(reports: &[PartReport]) {
+    match reports[0].timings {
+        Some(timings) => println!(
+            "Day {} (parsed in {:.2?}{})",
+            reports[0].day,
+            timings.parse(),
+            allocs_text(timings.parse_allocs)
+        ),
+        None if reports[0].status == Status::Failed => println!("Day {} (failed)", reports[0].day),
+        None => println!("Day {} (did not stop)", reports[0].day),
+    }
+    for report in reports {
+        let answer = report.answer.as_deref().unwrap_or(match report.status {
+            Status::Failed => "failed",
+            _ => "timed out",
+        });
+        match report.timings {
+            Some(timings) => println!(
+                "Part {}: {answer} ({:.2?}{})",
+                report.part,
+                timings.part(),
+                allocs_text(timings.part_allocs)
+            ),
+            None => println!("Part {}: {answer}", report.part),
+        }
+    }
+}
+
+fn allocs_text(stats: Option<AllocStats>) -> String {
+    let Some(stats) = stats else {
+        return String::new();
+    };
+
+    format!(
+        ", {} allocs of {}, peak {}",
+        HumanCount(stats.allocs),
+        HumanBytes(stats.bytes),
+        HumanBytes(stats.peak_bytes)
+    )
+}
+
+fn gen(args: GenArgs) {
+    let params = Params {
+        size: args.size,
+        seed_ranges: args.seed_ranges,
+        range_len: args.range_len,
+        loop_len: args.loop_len,
+        density: args.density,
+    };
+    let mut rng = ChaCha8Rng::seed_from_u64(args.seed);
+
+    if let Some(limit) = size_limit(args.day).filter(|&limit| args.size > limit) {
+        eprintln!(
+            "Day {} generates at most {limit} items, using --size {limit}",
+            args.day
+        );
+    }
+    let input = match generate(args.day, &params, &mut rng) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("Cannot generate day {}: {err}", args.day);
+            std::process::exit(1);
+        }
+    };
+
+    match &args.output {
+        Some(path) => std::fs::write(path, &input).unwrap(),
+        None => print!("{input}"),
+    }
+
+    if args.answer {
+        let (part_1, part_2) =
+            reference_answers(args.day, &input, &*args.progress.backend(false)).unwrap();
+        eprintln!("Part 1: {part_1}");
+        eprintln!("Part 2: {part_2}");
+    }
+}
+
+// The day goes into the crate this binary was built from, wherever it's run.
+fn new(args: NewArgs) {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+
+    match new_day(root, args.day) {
+        Ok(written) => {
+            for path in written {
+                println!(
+                    "Wrote {}",
+                    path.strip_prefix(root).unwrap_or(&path).display()
+                );
+            }
+        }
+        Err(err) => {
+            eprintln!("Cannot add day {}: {err}", args.day);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn viz(args: VizArgs) {
+    let (path, input) = match &args.file {
+        Some(path) => (path.clone(), std::fs::read_to_string(path)),
+        None => (
+            input_path(args.day, &args.input),
+            read_input_variant(args.day, &args.input),
+        ),
+    };
+    let input = input.unwrap_or_else(|err| {
+        eprintln!("Cannot read {}: {err}", path.display());
+        std::process::exit(1);
+    });
+
+    let viz = match visualizer(args.day, &input) {
+        Ok(Some(viz)) => viz,
+        Ok(None) => {
+            eprintln!("Day {} has no visualization", args.day);
+            std::process::exit(1);
+        }
+        Err(err) => {
+            eprintln!("Cannot visualize day {}: {err}", args.day);
+            std::process::exit(1);
+        }
+    };
+    let Some(output) = &args.output else {
+        if let Err(err) = tui::run(&*viz, args.fps) {
+            eprintln!("Cannot show day {}: {err}", args.day);
+            std::process::exit(1);
+        }
+        return;
+    };
+
+    let Some(format) = Animation::from_path(output) else {
+        eprintln!(
+            "Cannot tell the format of {}; use .gif or .png",
+            output.display()
+        );
+        std::process::exit(1);
+    };
+    let mut palette = match args.palette {
+        PaletteName::Dark => Palette::dark(),
+        PaletteName::Light => Palette::light(),
+    };
+    for spec in &args.colour {
+        if let Err(err) = palette.set(spec) {
+            eprintln!("Bad --colour: {err}");
+            std::process::exit(1);
+        }
+    }
+    let options = Options {
+        cell: args.cell,
+        palette,
+        fps: args.fps,
+        every: args.every,
+    };
+
+    // Whatever a failed export did write is no use, so it isn't left behind.
+    let written = File::create(output).and_then(|file| {
+        export(&*viz, format, &options, BufWriter::new(file)).inspect_err(|_| {
+            let _ = std::fs::remove_file(output);
+        })
+    });
+    match written {
+        Ok(frames) => println!("Wrote {frames} frames to {}", output.display()),
+        Err(err) => {
+            eprintln!("Cannot write {}: {err}", output.display());
+            std::process::exit(1);
+        }
+    }
+}
+
+fn fetch(args: FetchArgs) {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let fetcher = Cached::new(
+        Http::new(std::env::var("AOC_SESSION").ok()),
+        root.join(".cache/pages"),
+    );
+
+    let url = puzzle_url(args.day);
+    if args.refresh {
+        fetcher.forget(&url).unwrap();
+    }
+    let page = fetcher.get(&url).unwrap_or_else(|err| {
+        eprintln!("Cannot fetch {url}: {err}");
+        std::process::exit(1);
+    });
+
+    let found = examples_from(&parse_page(&page));
+    if found.is_empty() {
+        eprintln!("No examples with answers on {url}");
+        std::process::exit(1);
+    }
+
+    // Fetched examples are guesses until someone has looked at them, so they wait
+    // where the examples test won't check them and never replace one that may
+    // already be waiting.
+    let dir = fetched_dir(&examples_dir(), args.day);
+    let names: Vec<String> = (1..=found.len()).map(|i| format!("page_{i}")).collect();
+    if let Some(name) = names
+        .iter()
+        .find(|name| dir.join(format!("{name}.txt")).exists())
+    {
+        eprintln!(
+            "{} already exists; remove it to fetch again",
+            dir.join(format!("{name}.txt")).display()
+        );
+        std::process::exit(1);
+    }
+
+    for (name, (input, expected)) in names.iter().zip(&found) {
+        examples::write(&dir, name, input, expected).unwrap();
+        println!(
+            "Wrote {} with {}",
+            dir.join(format!("{name}.txt"))
+                .strip_prefix(root)
+                .unwrap()
+                .display(),
+            serde_json::to_string(expected).unwrap()
+        );
+    }
+    println!(
+        "Check them, add any params the puzzle's example uses, and move them up to {} for the examples test",
+        dir.parent().unwrap().strip_prefix(root).unwrap().display()
+    );
+}
+
+fn load_runs() -> Vec<Run> {
+    runlog::load(Path::new(RUN_LOG)).unwrap_or_else(|err| {
+        eprintln!("Cannot read {RUN_LOG}: {err}");
+        std::process::exit(1);
+    })
+}
+
+fn describe(number: usize, run: &Run) -> String {
+    format!(
+        "Run {number}: {}, {} at {} ({}), {} input",
+        run.timestamp, run.version, run.commit, run.profile, run.input
+    )
+}
+
+fn log() {
+    for (idx, run) in load_runs().iter().enumerate() {
+        let mut days: Vec<u8> = run.parts.iter().map(|p| p.day).collect();
+        days.dedup();
+        println!("{}, days {}", describe(idx + 1, run), list_days(&days));
+    }
+}
+
+fn diff_runs(args: DiffArgs) {
+    let runs = load_runs();
+    let after = args.after.unwrap_or(runs.len());
+    let before = args.before.unwrap_or(after.saturating_sub(1));
+
+    let get = |number: usize| {
+        runs.get(number.wrapping_sub(1)).unwrap_or_else(|| {
+            eprintln!("No run {number}; the log has {}", runs.len());
+            std::process::exit(1);
+        })
+    };
+    let (old, new) = (get(before), get(after));
+    println!("{}", describe(before, old));
+    println!("{}", describe(after, new));
+
+    for part in diff(old, new) {
+        let answer = |p: Option<&LoggedPart>| {
+            p.and_then(|p| p.answer.clone())
+                .unwrap_or_else(|| "-".to_string())
+        };
+        let time = |p: Option<&LoggedPart>| {
+            p.and_then(|p| p.part_ns).map_or("-".to_string(), |ns| {
+                format!("{:.2?}", Duration::from_nanos(ns))
+            })
+        };
+
+        let mut line = format!("Day {} part {}: ", part.day, part.part);
+        match (part.before, part.after) {
+            (None, _) => {
+                println!("{line}only in run {after}");
+                continue;
+            }
+            (_, None) => {
+                println!("{line}only in run {before}");
+                continue;
+            }
+            _ => {}
+        }
+        if part.answer_changed() {
+            line += &format!(
+                "{} -> {} (changed)",
+                answer(part.before),
+                answer(part.after)
+            );
+        } else {
+            line += &answer(part.after);
+        }
+        if part.input_changed() {
+            line += ", input changed";
+        }
+
+        line += &format!(", {} -> {}", time(part.before), time(part.after));
+        let ns = |p: Option<&LoggedPart>| p.and_then(|p| p.part_ns).filter(|&ns| ns > 0);
+        if let (Some(old), Some(new)) = (ns(part.before), ns(part.after)) {
+            line += &format!(" ({:.2}x)", new as f64 / old as f64);
+        }
+        println!("{line}");
+    }
+}
@@ -0,0 +1,54 @@
+use rayon::prelude::*;
+use std::ops::Range;
+
+// Sizes the pool shared by the runner and by days that split up their own work.
+// Only the first call has any effect; without one, rayon uses a thread per CPU.
+pub fn init(threads: usize) {
+    let _ = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build_global();
+}
+
+// Splits every range into chunks of at most `chunk` numbers, tagged with the index
+// of the range they came from. One huge range still spreads across the whole pool,
+// and many small ones don't each need a thread of their own.
+pub fn par_chunks(
+    ranges: &[Range<u64>],
+    chunk: u64,
+) -> impl ParallelIterator<Item = (usize, Range<u64>)> + '_ {
+    let chunk = chunk.max(1);
+
+    ranges
+        .par_iter()
+        .enumerate()
+        .flat_map_iter(move |(idx, range)| {
+            (range.start..range.end)
+                .step_by(chunk as usize)
+                .map(move |start| (idx, start..range.end.min(start.saturating_add(chunk))))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parallel::par_chunks;
+    use rayon::prelude::*;
+
+    #[test]
+    fn test_par_chunks() {
+        let ranges = [0..10, 20..21, 30..30, u64::MAX - 5..u64::MAX];
+        let mut chunks: Vec<_> = par_chunks(&ranges, 4).collect();
+        chunks.sort_by_key(|(idx, chunk)| (*idx, chunk.start));
+
+        assert_eq!(
+            vec![
+                (0, 0..4),
+                (0, 4..8),
+                (0, 8..10),
+                (1, 20..21),
+                (3, u64::MAX - 5..u64::MAX - 1),
+                (3, u64::MAX - 1..u64::MAX),
+            ],
+            chunks
+        );
+    }
+}
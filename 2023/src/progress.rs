@@ -0,0 +1,185 @@
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+// Somewhere for long running parts to report how far along they are. Each piece of
+// work gets its own task, which may be advanced from several threads at once.
+pub trait Progress: Send + Sync {
+    fn start(&self, label: String, total: u64) -> Box<dyn Task + '_>;
+}
+
+pub trait Task: Send + Sync {
+    fn inc(&self, delta: u64);
+
+    fn finish(&self, message: String);
+}
+
+// Live progress bars on the terminal.
+pub struct Bars {
+    multi: MultiProgress,
+    style: ProgressStyle,
+}
+
+impl Bars {
+    pub fn new() -> Self {
+        let style = ProgressStyle::with_template(
+            "[{elapsed_precise}] {bar:40.cyan/blue} {percent}% ({human_pos:>7}/{human_len:7}) {msg}",
+        )
+        .unwrap();
+
+        Bars {
+            multi: MultiProgress::new(),
+            style,
+        }
+    }
+}
+
+impl Default for Bars {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Progress for Bars {
+    fn start(&self, label: String, total: u64) -> Box<dyn Task + '_> {
+        let bar = self.multi.add(ProgressBar::new(total));
+        bar.set_style(self.style.clone());
+        bar.set_message(label.clone());
+
+        Box::new(BarTask { bar, label })
+    }
+}
+
+struct BarTask {
+    bar: ProgressBar,
+    label: String,
+}
+
+impl Task for BarTask {
+    fn inc(&self, delta: u64) {
+        self.bar.inc(delta);
+    }
+
+    fn finish(&self, message: String) {
+        self.bar
+            .finish_with_message(format!("{}: {message}", self.label));
+    }
+}
+
+// A line on stderr each time a task passes another tenth of its total, for logs
+// and other places without a terminal to redraw.
+pub struct Log;
+
+impl Progress for Log {
+    fn start(&self, label: String, total: u64) -> Box<dyn Task + '_> {
+        Box::new(LogTask {
+            label,
+            total,
+            done: AtomicU64::new(0),
+        })
+    }
+}
+
+struct LogTask {
+    label: String,
+    total: u64,
+    done: AtomicU64,
+}
+
+impl LogTask {
+    // Tenths of the total reached after `done` units, in u128 so totals near
+    // u64::MAX can't overflow. An empty task is complete from the start.
+    fn tenths(&self, done: u64) -> u128 {
+        if self.total == 0 {
+            return 10;
+        }
+
+        (done as u128 * 10 / self.total as u128).min(10)
+    }
+}
+
+impl Task for LogTask {
+    fn inc(&self, delta: u64) {
+        let before = self
+            .done
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |done| {
+                Some(done.saturating_add(delta))
+            })
+            .unwrap();
+        let after = before.saturating_add(delta);
+
+        let tenths = self.tenths(after);
+        if tenths > self.tenths(before) {
+            eprintln!("{}: {}% ({after}/{})", self.label, tenths * 10, self.total);
+        }
+    }
+
+    fn finish(&self, message: String) {
+        eprintln!("{}: {message}", self.label);
+    }
+}
+
+// Reports nothing, for tests and machine readable output.
+pub struct Silent;
+
+impl Progress for Silent {
+    fn start(&self, _label: String, _total: u64) -> Box<dyn Task + '_> {
+        Box::new(Silent)
+    }
+}
+
+impl Task for Silent {
+    fn inc(&self, _delta: u64) {}
+
+    fn finish(&self, _message: String) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::progress::{Log, LogTask, Progress, Silent};
+    use std::sync::atomic::AtomicU64;
+
+    #[test]
+    fn test_log_tenths() {
+        let tiny = LogTask {
+            label: "tiny".to_string(),
+            total: 3,
+            done: AtomicU64::new(0),
+        };
+        assert_eq!(
+            (0, 3, 6, 10),
+            (
+                tiny.tenths(0),
+                tiny.tenths(1),
+                tiny.tenths(2),
+                tiny.tenths(3)
+            )
+        );
+
+        let huge = LogTask {
+            label: "huge".to_string(),
+            total: u64::MAX,
+            done: AtomicU64::new(0),
+        };
+        assert_eq!(5, huge.tenths(u64::MAX / 2 + 1));
+        assert_eq!(10, huge.tenths(u64::MAX));
+
+        let empty = LogTask {
+            label: "empty".to_string(),
+            total: 0,
+            done: AtomicU64::new(0),
+        };
+        assert_eq!(10, empty.tenths(0));
+    }
+
+    #[test]
+    fn test_backends_accept_any_total() {
+        for total in [0, 1, 99, 100, u64::MAX] {
+            for progress in [&Log as &dyn Progress, &Silent] {
+                let task = progress.start(format!("total {total}"), total);
+                task.inc(1);
+                task.inc(u64::MAX);
+                task.finish("done".to_string());
+            }
+        }
+    }
+}
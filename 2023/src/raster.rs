@@ -0,0 +1,285 @@
+use crate::visualize::{Frame, Shade};
+
+pub type Rgb = [u8; 3];
+
+// An RGB image drawn a grid cell at a time, each cell `cell` pixels square.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Canvas {
+    pub width: usize,
+    pub height: usize,
+    pub cell: usize,
+    pub data: Vec<u8>,
+}
+
+impl Canvas {
+    // Room for `cols` by `rows` cells of `background`.
+    pub fn new(cols: usize, rows: usize, cell: usize, background: Rgb) -> Self {
+        let (width, height) = (cols * cell, rows * cell);
+        Canvas {
+            width,
+            height,
+            cell,
+            data: background.repeat(width * height),
+        }
+    }
+
+    pub fn pixel(&self, px: usize, py: usize) -> Rgb {
+        let idx = (py * self.width + px) * 3;
+        [self.data[idx], self.data[idx + 1], self.data[idx + 2]]
+    }
+
+    fn paint(&mut self, px: usize, py: usize, rgb: Rgb) {
+        let idx = (py * self.width + px) * 3;
+        self.data[idx..idx + 3].copy_from_slice(&rgb);
+    }
+
+    pub fn fill_cell(&mut self, x: usize, y: usize, rgb: Rgb) {
+        for py in y * self.cell..(y + 1) * self.cell {
+            for px in x * self.cell..(x + 1) * self.cell {
+                self.paint(px, py, rgb);
+            }
+        }
+    }
+
+    // Lines from the middle of the cell to the middle of each of its sides in
+    // `directions`, the way pipes join up with their neighbours.
+    pub fn stroke(&mut self, x: usize, y: usize, directions: &[(isize, isize)], rgb: Rgb) {
+        let (ox, oy) = ((x * self.cell) as isize, (y * self.cell) as isize);
+        let last = self.cell as isize - 1;
+        let half = (self.cell / 2) as isize;
+
+        for (dx, dy) in directions {
+            for step in 0..=half {
+                let px = (ox + half + dx * step).clamp(ox, ox + last);
+                let py = (oy + half + dy * step).clamp(oy, oy + last);
+                self.paint(px as usize, py as usize, rgb);
+            }
+        }
+    }
+
+    // Box-drawing glyphs are stroked, blanks and dots left out, and anything else
+    // stands in as a block half the size of the cell.
+    pub fn draw_glyph(&mut self, x: usize, y: usize, glyph: char, rgb: Rgb) {
+        if let Some(directions) = box_directions(glyph) {
+            self.stroke(x, y, directions, rgb);
+            return;
+        }
+        if glyph == ' ' || glyph == '.' {
+            return;
+        }
+
+        let margin = self.cell / 4;
+        let end = (self.cell - margin).max(margin + 1);
+        for py in y * self.cell + margin..y * self.cell + end {
+            for px in x * self.cell + margin..x * self.cell + end {
+                self.paint(px, py, rgb);
+            }
+        }
+    }
+}
+
+fn box_directions(glyph: char) -> Option<&'static [(isize, isize)]> {
+    Some(match glyph {
+        '│' => &[(0, -1), (0, 1)],
+        '─' => &[(-1, 0), (1, 0)],
+        '└' => &[(0, -1), (1, 0)],
+        '┘' => &[(-1, 0), (0, -1)],
+        '┐' => &[(-1, 0), (0, 1)],
+        '┌' => &[(0, 1), (1, 0)],
+        _ => return None,
+    })
+}
+
+// What a cell of some shade is filled with, and what its glyph is drawn in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Colours {
+    pub fill: Rgb,
+    pub ink: Rgb,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    pub background: Rgb,
+    pub dim: Colours,
+    pub plain: Colours,
+    pub marked: Colours,
+    pub active: Colours,
+    pub filled: Colours,
+}
+
+impl Palette {
+    pub fn dark() -> Self {
+        let background = [24, 24, 32];
+        let ink = |ink| Colours {
+            fill: background,
+            ink,
+        };
+        Palette {
+            background,
+            dim: ink([80, 80, 96]),
+            plain: ink([200, 200, 200]),
+            marked: ink([255, 204, 0]),
+            active: Colours {
+                fill: [255, 204, 0],
+                ink: [0, 0, 0],
+            },
+            filled: Colours {
+                fill: [60, 160, 80],
+                ink: [0, 0, 0],
+            },
+        }
+    }
+
+    pub fn light() -> Self {
+        let background = [250, 250, 245];
+        let ink = |ink| Colours {
+            fill: background,
+            ink,
+        };
+        Palette {
+            background,
+            dim: ink([200, 200, 200]),
+            plain: ink([60, 60, 60]),
+            marked: ink([200, 80, 0]),
+            active: Colours {
+                fill: [255, 170, 0],
+                ink: [0, 0, 0],
+            },
+            filled: Colours {
+                fill: [120, 200, 120],
+                ink: [20, 60, 20],
+            },
+        }
+    }
+
+    pub fn colours(&self, shade: Shade) -> Colours {
+        match shade {
+            Shade::Dim => self.dim,
+            Shade::Plain => self.plain,
+            Shade::Marked => self.marked,
+            Shade::Active => self.active,
+            Shade::Filled => self.filled,
+        }
+    }
+
+    // Overrides one entry from `background=#rrggbb` or `<shade>=<ink>[/<fill>]`,
+    // e.g. `active=#000000/#ffcc00`.
+    pub fn set(&mut self, spec: &str) -> Result<(), String> {
+        let (name, value) = spec
+            .split_once('=')
+            .ok_or_else(|| format!("expected NAME=COLOUR, got {spec:?}"))?;
+
+        if name == "background" {
+            self.background = rgb(value)?;
+            return Ok(());
+        }
+        let colours = match name {
+            "dim" => &mut self.dim,
+            "plain" => &mut self.plain,
+            "marked" => &mut self.marked,
+            "active" => &mut self.active,
+            "filled" => &mut self.filled,
+            _ => return Err(format!("no colour called {name:?}")),
+        };
+        match value.split_once('/') {
+            Some((ink, fill)) => {
+                colours.ink = rgb(ink)?;
+                colours.fill = rgb(fill)?;
+            }
+            None => colours.ink = rgb(value)?,
+        }
+        Ok(())
+    }
+
+    // Every distinct colour, background first, for formats that index their pixels.
+    pub fn table(&self) -> Vec<Rgb> {
+        let mut table = vec![self.background];
+        for shade in [
+            Shade::Dim,
+            Shade::Plain,
+            Shade::Marked,
+            Shade::Active,
+            Shade::Filled,
+        ] {
+            let Colours { fill, ink } = self.colours(shade);
+            for rgb in [fill, ink] {
+                if !table.contains(&rgb) {
+                    table.push(rgb);
+                }
+            }
+        }
+        table
+    }
+}
+
+fn rgb(hex: &str) -> Result<Rgb, String> {
+    let digits = hex.strip_prefix('#').unwrap_or(hex);
+    let channel = |at: usize| {
+        digits
+            .get(at..at + 2)
+            .and_then(|c| u8::from_str_radix(c, 16).ok())
+    };
+    match (digits.len(), channel(0), channel(2), channel(4)) {
+        (6, Some(r), Some(g), Some(b)) => Ok([r, g, b]),
+        _ => Err(format!("expected a colour like #ffcc00, got {hex:?}")),
+    }
+}
+
+// Draws a frame onto a canvas of `cols` by `rows` cells, so that every frame of an
+// animation can share the size of its largest one.
+pub fn render(frame: &Frame, cols: usize, rows: usize, cell: usize, palette: &Palette) -> Canvas {
+    let mut canvas = Canvas::new(cols, rows, cell, palette.background);
+
+    for (y, row) in frame.rows.iter().enumerate().take(rows) {
+        for (x, c) in row.iter().enumerate().take(cols) {
+            let Colours { fill, ink } = palette.colours(c.shade);
+            canvas.fill_cell(x, y, fill);
+            canvas.draw_glyph(x, y, c.glyph, ink);
+        }
+    }
+    canvas
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::raster::{render, Palette};
+    use crate::visualize::{Frame, Shade};
+
+    #[test]
+    fn test_render() {
+        let mut frame = Frame::new("");
+        frame.push_text("─", Shade::Plain);
+        frame.push_text("#", Shade::Active);
+        let palette = Palette::dark();
+
+        let canvas = render(&frame, 3, 2, 4, &palette);
+        assert_eq!((12, 8), (canvas.width, canvas.height));
+        assert_eq!(12 * 8 * 3, canvas.data.len());
+
+        // The pipe runs through the middle of its cell, the block sits inside its
+        // cell's fill, and cells past the frame are background.
+        assert_eq!(palette.plain.ink, canvas.pixel(0, 2));
+        assert_eq!(palette.plain.fill, canvas.pixel(0, 0));
+        assert_eq!(palette.active.ink, canvas.pixel(5, 1));
+        assert_eq!(palette.active.fill, canvas.pixel(4, 0));
+        assert_eq!(palette.background, canvas.pixel(9, 6));
+    }
+
+    #[test]
+    fn test_palette() {
+        let mut palette = Palette::dark();
+        palette.set("active=#000000/#FFCC00").unwrap();
+        palette.set("background=102030").unwrap();
+        assert_eq!([0, 0, 0], palette.active.ink);
+        assert_eq!([255, 204, 0], palette.active.fill);
+        assert_eq!([16, 32, 48], palette.background);
+
+        assert!(palette.set("active").is_err());
+        assert!(palette.set("bold=#ffffff").is_err());
+        assert!(palette.set("dim=#fff").is_err());
+
+        let table = palette.table();
+        assert_eq!(palette.background, table[0]);
+        assert!(table.len() <= 11);
+    }
+}
@@ -0,0 +1,220 @@
+use crate::runner::{PartReport, Status, GIT_COMMIT, PROFILE, VERSION};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// One `aoc2023 run`, as kept in the run log: which code ran it and when, and what
+// each part answered for which input.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Run {
+    pub timestamp: String,
+    pub version: String,
+    pub commit: String,
+    pub profile: String,
+    pub input: String,
+    pub parts: Vec<LoggedPart>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LoggedPart {
+    pub day: u8,
+    pub part: u8,
+    pub status: Status,
+    pub answer: Option<String>,
+    pub input_sha256: Option<String>,
+    pub parse_ns: Option<u64>,
+    pub part_ns: Option<u64>,
+}
+
+impl Run {
+    pub fn new(started: SystemTime, input: &str, reports: &[PartReport]) -> Self {
+        Run {
+            timestamp: utc(started),
+            version: VERSION.to_string(),
+            commit: GIT_COMMIT.to_string(),
+            profile: PROFILE.to_string(),
+            input: input.to_string(),
+            parts: reports
+                .iter()
+                .map(|report| LoggedPart {
+                    day: report.day,
+                    part: report.part,
+                    status: report.status,
+                    answer: report.answer.clone(),
+                    input_sha256: report.input_sha256.clone(),
+                    parse_ns: report.timings.map(|t| t.parse_ns),
+                    part_ns: report.timings.map(|t| t.part_ns),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn part(&self, day: u8, part: u8) -> Option<&LoggedPart> {
+        self.parts.iter().find(|p| (p.day, p.part) == (day, part))
+    }
+}
+
+// The log is one JSON line per run, oldest first.
+pub fn append(path: &Path, run: &Run) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(
+        file,
+        "{}",
+        serde_json::to_string(run).map_err(io::Error::other)?
+    )
+}
+
+pub fn load(path: &Path) -> io::Result<Vec<Run>> {
+    fs::read_to_string(path)?
+        .lines()
+        .map(|line| serde_json::from_str(line).map_err(io::Error::other))
+        .collect()
+}
+
+// A part that either run has, next to how the other one did it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct PartDiff<'a> {
+    pub day: u8,
+    pub part: u8,
+    pub before: Option<&'a LoggedPart>,
+    pub after: Option<&'a LoggedPart>,
+}
+
+impl PartDiff<'_> {
+    pub fn answer_changed(&self) -> bool {
+        self.before.map(|p| &p.answer) != self.after.map(|p| &p.answer)
+    }
+
+    pub fn input_changed(&self) -> bool {
+        self.before.map(|p| &p.input_sha256) != self.after.map(|p| &p.input_sha256)
+    }
+}
+
+pub fn diff<'a>(before: &'a Run, after: &'a Run) -> Vec<PartDiff<'a>> {
+    let mut keys: Vec<(u8, u8)> = before
+        .parts
+        .iter()
+        .chain(&after.parts)
+        .map(|p| (p.day, p.part))
+        .collect();
+    keys.sort();
+    keys.dedup();
+
+    keys.into_iter()
+        .map(|(day, part)| PartDiff {
+            day,
+            part,
+            before: before.part(day, part),
+            after: after.part(day, part),
+        })
+        .collect()
+}
+
+// An RFC 3339 timestamp in UTC, worked out from the days since the epoch.
+pub fn utc(time: SystemTime) -> String {
+    let secs = time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let (days, secs) = (secs / 86_400, secs % 86_400);
+
+    // From Howard Hinnant's `civil_from_days`, with eras of 400 years starting in
+    // March so that leap days fall at the end.
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runlog::{append, diff, load, utc, LoggedPart, Run};
+    use crate::runner::Status;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn part(day: u8, part: u8, answer: &str, sha: &str) -> LoggedPart {
+        LoggedPart {
+            day,
+            part,
+            status: Status::Finished,
+            answer: Some(answer.to_string()),
+            input_sha256: Some(sha.to_string()),
+            parse_ns: Some(10),
+            part_ns: Some(100),
+        }
+    }
+
+    fn run(parts: Vec<LoggedPart>) -> Run {
+        Run {
+            timestamp: utc(UNIX_EPOCH),
+            version: "0.1.0".to_string(),
+            commit: "abc1234".to_string(),
+            profile: "release".to_string(),
+            input: "real".to_string(),
+            parts,
+        }
+    }
+
+    #[test]
+    fn test_utc() {
+        assert_eq!("1970-01-01T00:00:00Z", utc(UNIX_EPOCH));
+        assert_eq!(
+            "2023-12-01T05:00:00Z",
+            utc(UNIX_EPOCH + Duration::from_secs(1_701_406_800))
+        );
+        assert_eq!(
+            "2024-02-29T23:59:59Z",
+            utc(UNIX_EPOCH + Duration::from_secs(1_709_251_199))
+        );
+    }
+
+    #[test]
+    fn test_diff() {
+        let before = run(vec![part(1, 1, "142", "aa"), part(1, 2, "281", "aa")]);
+        let after = run(vec![
+            part(1, 1, "142", "aa"),
+            part(1, 2, "280", "bb"),
+            part(2, 1, "8", "cc"),
+        ]);
+
+        let diffs = diff(&before, &after);
+        assert_eq!(
+            vec![(1, 1), (1, 2), (2, 1)],
+            diffs.iter().map(|d| (d.day, d.part)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(false, false), (true, true), (true, true)],
+            diffs
+                .iter()
+                .map(|d| (d.answer_changed(), d.input_changed()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(None, diffs[2].before);
+    }
+
+    #[test]
+    fn test_log_round_trip() {
+        let path = std::env::temp_dir().join(format!("aoc2023-runs-{}.jsonl", std::process::id()));
+        let runs = [
+            run(vec![part(1, 1, "142", "aa")]),
+            run(vec![part(1, 1, "143", "aa")]),
+        ];
+        for r in &runs {
+            append(&path, r).unwrap();
+        }
+
+        assert_eq!(runs.to_vec(), load(&path).unwrap());
+        std::fs::remove_file(path).unwrap();
+    }
+}
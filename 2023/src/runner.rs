@@ -0,0 +1,395 @@
+use crate::alloc::{self, AllocStats};
+use crate::cancel::Cancel;
+use crate::progress::Progress;
+use crate::read_input_variant;
+use crate::solution::{solution, Answer, Context, Parse, Solution};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+pub const GIT_COMMIT: &str = env!("AOC_GIT_COMMIT");
+pub const PROFILE: &str = env!("AOC_PROFILE");
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// One part of one day's run, flat so that each can be written as a single JSON
+// line. A part that timed out has no answer, and no timings either if its day
+// never stopped at all. A day that couldn't run has neither, and says why.
+#[derive(Debug, Clone, Serialize)]
+pub struct PartReport {
+    pub day: u8,
+    pub part: u8,
+    pub status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub answer: Option<String>,
+    pub answer_type: Option<&'static str>,
+    pub timings: Option<Timings>,
+    pub input: String,
+    pub input_sha256: Option<String>,
+    pub commit: &'static str,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Finished,
+    TimedOut,
+    Failed,
+}
+
+// Parsing is shared by both parts of a day, so each part reports the same parse
+// time next to its own. Allocations are only there when counting is enabled.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Timings {
+    pub parse_ns: u64,
+    pub part_ns: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parse_allocs: Option<AllocStats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_allocs: Option<AllocStats>,
+}
+
+impl Timings {
+    pub fn parse(&self) -> Duration {
+        Duration::from_nanos(self.parse_ns)
+    }
+
+    pub fn part(&self) -> Duration {
+        Duration::from_nanos(self.part_ns)
+    }
+}
+
+// Both parts run at once on the shared pool, each timed on its own. A part that
+// gives up because `ctx.cancel` was set is reported as timed out.
+pub fn run_day(
+    day: u8,
+    parse: Parse,
+    input: &str,
+    variant: &str,
+    ctx: &Context,
+) -> Vec<PartReport> {
+    let input_sha256 = input_sha256(input);
+
+    let start = Instant::now();
+    let (solution, parse_allocs) = alloc::measure(|| parse(input));
+    let parse_ns = start.elapsed().as_nanos() as u64;
+
+    let timed = |solve: fn(&dyn Solution, &Context) -> Option<Answer>| {
+        let start = Instant::now();
+        let (answer, allocs) = alloc::measure(|| solve(&*solution, ctx));
+        (answer, start.elapsed().as_nanos() as u64, allocs)
+    };
+    let (part_1, part_2) = rayon::join(
+        || timed(|s, ctx| s.part_1(ctx)),
+        || timed(|s, ctx| s.part_2(ctx)),
+    );
+
+    (1..)
+        .zip([part_1, part_2])
+        .map(|(part, (answer, part_ns, part_allocs))| PartReport {
+            day,
+            part,
+            status: if answer.is_some() {
+                Status::Finished
+            } else {
+                Status::TimedOut
+            },
+            error: None,
+            answer_type: answer.as_ref().map(|answer| answer.kind),
+            answer: answer.map(|answer| answer.value),
+            timings: Some(Timings {
+                parse_ns,
+                part_ns,
+                parse_allocs,
+                part_allocs,
+            }),
+            input: variant.to_string(),
+            input_sha256: Some(input_sha256.clone()),
+            commit: GIT_COMMIT,
+        })
+        .collect()
+}
+
+fn input_sha256(input: &str) -> String {
+    format!("{:x}", Sha256::digest(input))
+}
+
+// Stands in for both parts of a day that was still running well past its timeout,
+// or that couldn't run at all. The input's hash is kept if the day got as far as
+// reading it.
+fn unanswered(
+    day: u8,
+    variant: &str,
+    status: Status,
+    error: Option<String>,
+    input_sha256: Option<String>,
+) -> Vec<PartReport> {
+    (1..=2)
+        .map(|part| PartReport {
+            day,
+            part,
+            status,
+            error: error.clone(),
+            answer: None,
+            answer_type: None,
+            timings: None,
+            input: variant.to_string(),
+            input_sha256: input_sha256.clone(),
+            commit: GIT_COMMIT,
+        })
+        .collect()
+}
+
+// Runs a day, telling `hashed` about its input as soon as it has been read.
+fn run_one(
+    day: u8,
+    variant: &str,
+    ctx: &Context,
+    hashed: impl FnOnce(String),
+) -> Result<Vec<PartReport>, String> {
+    let Some(parse) = solution(day) else {
+        return Err("no solution".to_string());
+    };
+
+    let input = read_input_variant(day, variant)
+        .map_err(|err| format!("cannot read {variant} input: {err}"))?;
+    hashed(input_sha256(&input));
+
+    Ok(run_day(day, parse, &input, variant, ctx))
+}
+
+// How long past its timeout a day has to notice and stop before the runner stops
+// waiting for it, and how often it checks.
+const GRACE: Duration = Duration::from_secs(1);
+const TICK: Duration = Duration::from_millis(50);
+
+enum Event {
+    Started(usize, Instant),
+    Hashed(usize, String),
+    Done(usize, Result<Vec<PartReport>, String>),
+}
+
+// Runs every day with its input on the shared pool, returning each day's reports in
+// the order the days were given. A day that couldn't run, because it has no
+// solution or input or because it panicked, is reported as failed.
+//
+// With a timeout, each day is asked to stop once it has run that long. A day that
+// ignores it is given up on after a grace period and left running in the
+// background, so the caller should exit soon after rather than wait for the pool.
+// If every worker ends up held by such a day, the days still queued are given up
+// on too.
+pub fn run_days(
+    days: &[u8],
+    variant: &str,
+    progress: Arc<dyn Progress>,
+    timeout: Option<Duration>,
+) -> Vec<Vec<PartReport>> {
+    let (tx, rx) = mpsc::channel();
+    for (idx, &day) in days.iter().enumerate() {
+        let (tx, progress, variant) = (tx.clone(), progress.clone(), variant.to_string());
+        rayon::spawn(move || {
+            let _ = tx.send(Event::Started(idx, Instant::now()));
+
+            let cancel = timeout.map_or_else(Cancel::never, Cancel::after);
+            let ctx = Context {
+                progress: &*progress,
+                cancel: &cancel,
+                ..Context::default()
+            };
+            let hashed = |sha| {
+                let _ = tx.send(Event::Hashed(idx, sha));
+            };
+            let result =
+                panic::catch_unwind(AssertUnwindSafe(|| run_one(day, &variant, &ctx, hashed)))
+                    .unwrap_or_else(|payload| Err(panicked(&*payload)));
+            let _ = tx.send(Event::Done(idx, result));
+        });
+    }
+    drop(tx);
+
+    let mut started = vec![None; days.len()];
+    let mut hashes = vec![None; days.len()];
+    let mut results = vec![None; days.len()];
+    let mut last_event = Instant::now();
+    while results.iter().any(Option::is_none) {
+        let event = match timeout {
+            Some(_) => rx.recv_timeout(TICK),
+            None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        if event.is_ok() {
+            last_event = Instant::now();
+        }
+        match event {
+            Ok(Event::Started(idx, at)) => started[idx] = Some(at),
+            Ok(Event::Hashed(idx, sha)) => hashes[idx] = Some(sha),
+            Ok(Event::Done(idx, result)) => results[idx] = Some(result),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+
+        let Some(timeout) = timeout else {
+            continue;
+        };
+        for (idx, result) in results.iter_mut().enumerate() {
+            if result.is_some() {
+                continue;
+            }
+            match started[idx] {
+                Some(at) if at.elapsed() > timeout + GRACE => {
+                    *result = Some(Ok(unanswered(
+                        days[idx],
+                        variant,
+                        Status::TimedOut,
+                        None,
+                        hashes[idx].clone(),
+                    )));
+                }
+                None if last_event.elapsed() > timeout + GRACE => {
+                    *result = Some(Err(
+                        "never started, every worker was held by a day that timed out".to_string(),
+                    ));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    days.iter()
+        .zip(results)
+        .zip(hashes)
+        .map(|((&day, result), sha)| {
+            match result.unwrap_or_else(|| Err("stopped without reporting".to_string())) {
+                Ok(reports) => reports,
+                Err(err) => unanswered(day, variant, Status::Failed, Some(err), sha),
+            }
+        })
+        .collect()
+}
+
+fn panicked(payload: &(dyn Any + Send)) -> String {
+    let message = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown cause");
+
+    format!("panicked: {message}")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cancel::Cancel;
+    use crate::progress::Silent;
+    use crate::runner::{run_day, run_days, Status};
+    use crate::solution::{solution, Context};
+    use indoc::indoc;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_run_day() {
+        let input = indoc! {"
+            ...#......
+            .......#..
+            #.........
+            ..........
+            ......#...
+            .#........
+            .........#
+            ..........
+            .......#..
+            #...#.....
+        "};
+
+        let reports = run_day(
+            11,
+            solution(11).unwrap(),
+            input,
+            "example",
+            &Context::default(),
+        );
+
+        assert_eq!(2, reports.len());
+        assert_eq!(
+            (Some("374"), Some("u128")),
+            (reports[0].answer.as_deref(), reports[0].answer_type)
+        );
+        assert_eq!(
+            (11, 2, Some("82000210")),
+            (
+                reports[1].day,
+                reports[1].part,
+                reports[1].answer.as_deref()
+            )
+        );
+        assert_eq!(
+            reports[0].timings.unwrap().parse_ns,
+            reports[1].timings.unwrap().parse_ns
+        );
+
+        let json = serde_json::to_value(&reports[0]).unwrap();
+        assert_eq!("example", json["input"]);
+        assert_eq!(1, json["part"]);
+        assert_eq!("finished", json["status"]);
+        assert_eq!(
+            "d4bcb6ee06cca2e437afa47b583106835c71ab4cb100c45e27899dbafee55634",
+            json["input_sha256"]
+        );
+        assert!(json["timings"]["part_ns"].is_u64());
+    }
+
+    #[test]
+    fn test_run_day_cancelled() {
+        let input = indoc! {"
+            seeds: 79 14 55 13
+
+            seed-to-soil map:
+            50 98 2
+            52 50 48
+        "};
+
+        // Day 5 only looks at the token while mapping seed ranges in part 2.
+        let cancel = Cancel::never();
+        cancel.cancel();
+        let ctx = Context {
+            cancel: &cancel,
+            ..Context::default()
+        };
+        let reports = run_day(5, solution(5).unwrap(), input, "example", &ctx);
+
+        assert_eq!(
+            (Status::Finished, Some("13")),
+            (reports[0].status, reports[0].answer.as_deref())
+        );
+        assert_eq!(
+            (Status::TimedOut, None),
+            (reports[1].status, reports[1].answer.as_deref())
+        );
+        assert!(reports[1].timings.is_some());
+
+        let json = serde_json::to_value(&reports[1]).unwrap();
+        assert_eq!("timed_out", json["status"]);
+        assert!(json["answer"].is_null());
+    }
+
+    #[test]
+    fn test_run_days_failed() {
+        let reports = run_days(&[24], "real", Arc::new(Silent), None);
+
+        assert_eq!(1, reports.len());
+        assert_eq!(
+            vec![(24, 1, Status::Failed), (24, 2, Status::Failed)],
+            reports[0]
+                .iter()
+                .map(|r| (r.day, r.part, r.status))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(Some("no solution"), reports[0][0].error.as_deref());
+
+        let json = serde_json::to_value(&reports[0][1]).unwrap();
+        assert_eq!("failed", json["status"]);
+    }
+}
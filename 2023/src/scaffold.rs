@@ -0,0 +1,330 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// What `aoc2023 new` starts a day with. `{day}` is replaced with the day's number.
+// Its test skeleton checks the day's examples against their answers, so that they
+// can be run on their own while the day is being solved.
+const SOLUTION: &str = r#"use crate::solution::{Answer, Context, Solution};
+
+pub fn part_1(_lines: &[String]) -> u64 {
+    todo!()
+}
+
+pub fn part_2(_lines: &[String]) -> u64 {
+    todo!()
+}
+
+pub struct Day{day} {
+    lines: Vec<String>,
+}
+
+impl Solution for Day{day} {
+    fn parse(input: &str) -> Self {
+        Day{day} {
+            lines: input.lines().map(String::from).collect(),
+        }
+    }
+
+    fn part_1(&self, _ctx: &Context) -> Option<Answer> {
+        Some(part_1(&self.lines).into())
+    }
+
+    fn part_2(&self, _ctx: &Context) -> Option<Answer> {
+        Some(part_2(&self.lines).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::examples::{check, discover, examples_dir};
+
+    #[test]
+    fn test_examples() {
+        let examples = discover(&examples_dir()).unwrap();
+        let failures: Vec<String> = examples
+            .iter()
+            .filter(|example| example.day == {day})
+            .flat_map(check)
+            .collect();
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+}
+"#;
+
+const BIN: &str = r#"use aoc2023::day{day}::{part_1, part_2};
+use aoc2023::read_input_lines;
+
+fn main() {
+    let lines = read_input_lines({day});
+
+    println!("Part 1: {}", part_1(&lines));
+    println!("Part 2: {}", part_2(&lines));
+}
+"#;
+
+// Creates everything a new day needs in the crate at `root` and registers it with
+// the runner, returning the files it wrote. Refuses a day past the 25 a puzzle
+// year has, or one with any trace of itself already. Everything is checked before
+// the first write, and if a write still fails the files are put back as they were.
+pub fn new_day(root: &Path, day: u8) -> io::Result<Vec<PathBuf>> {
+    if !(1..=25).contains(&day) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("day {day} is not between 1 and 25"),
+        ));
+    }
+
+    let solution_path = root.join(format!("src/day{day}.rs"));
+    let bin_path = root.join(format!("src/bin/day{day}.rs"));
+    let lib_path = root.join("src/lib.rs");
+    let registry_path = root.join("src/solution.rs");
+    let examples_dir = root.join(format!("examples/day{day}"));
+
+    let exists = |what: String| Err(io::Error::new(io::ErrorKind::AlreadyExists, what));
+    for path in [&solution_path, &bin_path] {
+        if path.exists() {
+            return exists(format!("{} already exists", path.display()));
+        }
+    }
+    if !root.join("src/bin").is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{} is not a directory", root.join("src/bin").display()),
+        ));
+    }
+
+    let old_lib = fs::read_to_string(&lib_path)?;
+    let old_registry = fs::read_to_string(&registry_path)?;
+    let Some(lib) = register_module(&old_lib, day) else {
+        return exists(format!("day{day} is already a module of src/lib.rs"));
+    };
+    if old_registry.lines().any(|l| entry_day(l) == Some(day)) {
+        return exists(format!("day {day} is already in DAYS"));
+    }
+    let Some(registry) = register_day(&old_registry, day) else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "cannot find DAYS and its imports in src/solution.rs",
+        ));
+    };
+
+    // An example without answers is only parsed by the examples test until the
+    // puzzle's are filled in.
+    let example_path = examples_dir.join("example.txt");
+    let example = !example_path.exists();
+
+    let template = |source: &str| source.replace("{day}", &day.to_string());
+    let mut written = Vec::new();
+    let result = (|| {
+        for (path, contents) in [
+            (&solution_path, template(SOLUTION)),
+            (&bin_path, template(BIN)),
+        ] {
+            fs::write(path, contents)?;
+            written.push(path.clone());
+        }
+        if example {
+            fs::create_dir_all(&examples_dir)?;
+            for (path, contents) in [
+                (example_path, ""),
+                (examples_dir.join("example.json"), "{}\n"),
+            ] {
+                fs::write(&path, contents)?;
+                written.push(path);
+            }
+        }
+        fs::write(&lib_path, &lib)?;
+        fs::write(&registry_path, &registry)
+    })();
+
+    if let Err(err) = result {
+        for path in &written {
+            let _ = fs::remove_file(path);
+        }
+        let _ = fs::write(&lib_path, old_lib);
+        let _ = fs::write(&registry_path, old_registry);
+        return Err(err);
+    }
+
+    written.splice(2..2, [lib_path, registry_path]);
+    Ok(written)
+}
+
+// Adds `pub mod dayN;` to the block of modules at the top of lib.rs, kept in the
+// order rustfmt sorts them. None if it's already there.
+pub fn register_module(lib: &str, day: u8) -> Option<String> {
+    let name = format!("day{day}");
+    let module = format!("pub mod {name};");
+    let mut lines: Vec<&str> = lib.lines().collect();
+    if lines.contains(&&*module) {
+        return None;
+    }
+
+    let end = lines
+        .iter()
+        .take_while(|l| module_name(l).is_some())
+        .count();
+    let at = lines[..end]
+        .iter()
+        .position(|l| module_name(l).is_some_and(|m| m > &*name))
+        .unwrap_or(end);
+    lines.insert(at, &module);
+
+    Some(lines.join("\n") + "\n")
+}
+
+// Imports the day's module into solution.rs and adds it to DAYS in day order.
+// None if DAYS already has it.
+pub fn register_day(registry: &str, day: u8) -> Option<String> {
+    let entry = format!("    ({day}, boxed::<day{day}::Day{day}>),");
+    let mut lines: Vec<String> = registry.lines().map(String::from).collect();
+    if lines.contains(&entry) {
+        return None;
+    }
+
+    let start = lines.iter().position(|l| l.starts_with("pub const DAYS"))?;
+    let end = start + lines[start..].iter().position(|l| l == "];")?;
+    let at = (start + 1..end)
+        .find(|&i| entry_day(&lines[i]).is_some_and(|d| d > day))
+        .unwrap_or(end);
+    lines.insert(at, entry);
+    lines[start] = format!("pub const DAYS: [(u8, Parse); {}] = [", end - start);
+
+    let use_start = lines.iter().position(|l| l.starts_with("use crate::{"))?;
+    let use_end = use_start + lines[use_start..].iter().position(|l| l.ends_with("};"))?;
+    let mut modules: Vec<String> = lines[use_start..=use_end]
+        .join(" ")
+        .trim_start_matches("use crate::{")
+        .trim_end_matches("};")
+        .split(',')
+        .map(|m| m.trim().to_string())
+        .filter(|m| !m.is_empty())
+        .collect();
+    modules.push(format!("day{day}"));
+    modules.sort();
+    lines.splice(use_start..=use_end, use_lines(&modules));
+
+    Some(lines.join("\n") + "\n")
+}
+
+fn module_name(line: &str) -> Option<&str> {
+    line.strip_prefix("pub mod ")?.strip_suffix(';')
+}
+
+fn entry_day(line: &str) -> Option<u8> {
+    line.trim()
+        .strip_prefix('(')?
+        .split(',')
+        .next()?
+        .parse()
+        .ok()
+}
+
+// Lays out `use crate::{...};` the way rustfmt would: on one line if it fits in
+// 100 columns, otherwise with the names filling indented lines of their own.
+fn use_lines(modules: &[String]) -> Vec<String> {
+    let line = format!("use crate::{{{}}};", modules.join(", "));
+    if line.len() <= 100 {
+        return vec![line];
+    }
+
+    let mut lines = vec!["use crate::{".to_string()];
+    let mut current = String::new();
+    for module in modules {
+        if !current.is_empty() && 4 + current.len() + module.len() + 2 > 100 {
+            lines.push(format!("    {}", current.trim_end()));
+            current.clear();
+        }
+        current.push_str(module);
+        current.push_str(", ");
+    }
+    lines.push(format!("    {},", current.trim_end_matches(", ")));
+    lines.push("};".to_string());
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scaffold::{module_name, new_day, register_day, register_module, SOLUTION};
+    use crate::solution::DAYS;
+
+    // Registered into the real files, so the days added here are past the 25 a
+    // puzzle year can have.
+    const LIB: &str = include_str!("lib.rs");
+    const REGISTRY: &str = include_str!("solution.rs");
+
+    #[test]
+    fn test_register_module() {
+        assert_eq!(None, register_module(LIB, 5));
+
+        let lib = register_module(LIB, 30).unwrap();
+        let modules: Vec<&str> = lib.lines().filter_map(module_name).collect();
+        assert!(modules.windows(2).all(|w| w[0] < w[1]));
+        assert!(modules.contains(&"day30"));
+        assert_eq!(LIB.lines().count() + 1, lib.lines().count());
+    }
+
+    #[test]
+    fn test_register_day() {
+        assert_eq!(None, register_day(REGISTRY, 5));
+
+        let registry = register_day(REGISTRY, 40).unwrap();
+        let registry = register_day(&registry, 30).unwrap();
+        assert!(registry.contains(", day30, day4, day40, day5,"));
+        assert!(registry
+            .contains("    (30, boxed::<day30::Day30>),\n    (40, boxed::<day40::Day40>),\n];"));
+
+        // Enough days that the import no longer fits on one line.
+        let registry = (41..=50).fold(registry, |r, day| register_day(&r, day).unwrap());
+        assert!(registry.contains(&format!(
+            "pub const DAYS: [(u8, Parse); {}] = [",
+            DAYS.len() + 12
+        )));
+        assert!(registry.contains("use crate::{\n    day1, day10,"));
+        assert!(registry.lines().all(|l| l.len() <= 100));
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = std::env::temp_dir().join(format!("aoc2023-new-day-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/lib.rs"), LIB).unwrap();
+        std::fs::write(root.join("src/solution.rs"), REGISTRY).unwrap();
+
+        for day in [0, 26] {
+            let err = new_day(&root, day).unwrap_err();
+            assert_eq!(std::io::ErrorKind::InvalidInput, err.kind());
+        }
+
+        // Without src/bin the day is refused before anything is written.
+        assert!(new_day(&root, 20).is_err());
+        assert!(!root.join("src/day20.rs").exists());
+        assert_eq!(
+            LIB,
+            std::fs::read_to_string(root.join("src/lib.rs")).unwrap()
+        );
+
+        std::fs::create_dir_all(root.join("src/bin")).unwrap();
+        assert_eq!(
+            std::io::ErrorKind::AlreadyExists,
+            new_day(&root, 5).unwrap_err().kind()
+        );
+        assert_eq!(6, new_day(&root, 20).unwrap().len());
+        assert!(std::fs::read_to_string(root.join("src/lib.rs"))
+            .unwrap()
+            .contains("pub mod day20;"));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_template_names_the_day() {
+        let source = SOLUTION.replace("{day}", "12");
+        assert!(source.contains("impl Solution for Day12 {"));
+        assert!(source.contains("        Day12 {\n            lines:"));
+        assert!(source.contains(".filter(|example| example.day == 12)"));
+        assert!(!source.contains("{day}"));
+    }
+}
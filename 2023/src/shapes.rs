@@ -0,0 +1,133 @@
+pub(crate) const DIGIT_WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+// Day 1: the tokens run together with a numeric digit slotted in at `at`, so every
+// line has at least one.
+pub(crate) fn with_digit(mut tokens: Vec<String>, at: usize, digit: u32) -> String {
+    tokens.insert(at, digit.to_string());
+    tokens.concat()
+}
+
+pub(crate) const COLORS: [&str; 3] = ["red", "green", "blue"];
+
+// Day 2: a game from its rounds of (count, colour) picks.
+pub(crate) fn game_line(id: usize, rounds: &[Vec<(u32, &str)>]) -> String {
+    let rounds: Vec<String> = rounds
+        .iter()
+        .map(|picks| {
+            picks
+                .iter()
+                .map(|(num, color)| format!("{num} {color}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+        .collect();
+
+    format!("Game {id}: {}\n", rounds.join("; "))
+}
+
+// Day 4: a card with its numbers right aligned, as in the real input.
+pub(crate) fn card_line(id: usize, winning: &[u32], have: &[u32]) -> String {
+    format!(
+        "Card {id:>3}: {} | {}\n",
+        number_list(winning),
+        number_list(have)
+    )
+}
+
+fn number_list(nums: &[u32]) -> String {
+    nums.iter()
+        .map(|n| format!("{n:>2}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+const CATEGORIES: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+// Day 5: the (start, length) seed pairs, then a map of (dest, src, len) mappings
+// from each category to the next.
+pub(crate) fn almanac_text(seeds: &[(u64, u64)], maps: &[Vec<(u64, u64, u64)>]) -> String {
+    let seeds: Vec<String> = seeds.iter().map(|(s, l)| format!("{s} {l}")).collect();
+    let mut out = format!("seeds: {}\n\n", seeds.join(" "));
+
+    let mut blocks = Vec::new();
+    for (pair, mappings) in CATEGORIES.windows(2).zip(maps) {
+        let mut block = format!("{}-to-{} map:\n", pair[0], pair[1]);
+        for (dest, src, len) in mappings {
+            block.push_str(&format!("{dest} {src} {len}\n"));
+        }
+        blocks.push(block);
+    }
+    out.push_str(&blocks.join("\n"));
+
+    out
+}
+
+// Draws the pipes of a closed path into a grid with a one tile border, putting `S`
+// at `path[start]` and clearing stray pieces next to it so they cannot suggest
+// another start shape.
+pub(crate) fn draw_loop(grid: &mut [Vec<char>], path: &[(usize, usize)], start: usize) {
+    let n = path.len();
+    for (i, &(x, y)) in path.iter().enumerate() {
+        let step = |(nx, ny): (usize, usize)| (nx as isize - x as isize, ny as isize - y as isize);
+        let dirs = [step(path[(i + n - 1) % n]), step(path[(i + 1) % n])];
+        let has = |d: (isize, isize)| dirs.contains(&d);
+
+        grid[y + 1][x + 1] = match (has((0, -1)), has((1, 0)), has((0, 1)), has((-1, 0))) {
+            (true, false, true, false) => '|',
+            (false, true, false, true) => '-',
+            (true, true, false, false) => 'L',
+            (true, false, false, true) => 'J',
+            (false, false, true, true) => '7',
+            (false, true, true, false) => 'F',
+            _ => unreachable!(),
+        };
+    }
+
+    let (sx, sy) = path[start];
+    for (y, row) in grid.iter_mut().enumerate().skip(sy).take(3) {
+        for (x, cell) in row.iter_mut().enumerate().skip(sx).take(3) {
+            if x == 0 || y == 0 || !path.contains(&(x - 1, y - 1)) {
+                *cell = '.';
+            }
+        }
+    }
+    grid[sy + 1][sx + 1] = 'S';
+}
+
+// Tiles of the outline in order, clockwise from the bottom left corner. Column `c`
+// spans x in 2c..=2c+2, and the bottom edge sits at y = 2 * max_height.
+pub(crate) fn histogram_outline(heights: &[usize], max_height: usize) -> Vec<(usize, usize)> {
+    let top = |c: usize| 2 * (max_height - heights[c]);
+    let (right, bottom) = (2 * heights.len(), 2 * max_height);
+
+    let mut path: Vec<(usize, usize)> = (top(0)..=bottom).rev().map(|y| (0, y)).collect();
+
+    for c in 0..heights.len() {
+        let x = 2 * c;
+        if c > 0 {
+            let (from, to) = (top(c - 1), top(c));
+            if from < to {
+                path.extend((from + 1..=to).map(|y| (x, y)));
+            } else {
+                path.extend((to..from).rev().map(|y| (x, y)));
+            }
+        }
+        path.extend([(x + 1, top(c)), (x + 2, top(c))]);
+    }
+
+    path.extend((top(heights.len() - 1) + 1..=bottom).map(|y| (right, y)));
+    path.extend((1..right).rev().map(|x| (x, bottom)));
+
+    path
+}
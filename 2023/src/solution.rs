@@ -0,0 +1,100 @@
+use crate::cancel::Cancel;
+use crate::progress::{Progress, Silent};
+use crate::{day1, day10, day11, day2, day3, day4, day5, day6, day7, day8, day9};
+use num::BigUint;
+use std::collections::BTreeMap;
+use std::fmt;
+
+// An answer along with the name of the type the day computed it as, since some
+// answers don't fit in the integers other tools read numbers into.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answer {
+    pub value: String,
+    pub kind: &'static str,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.value)
+    }
+}
+
+macro_rules! impl_answer_from {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(value: $t) -> Self {
+                Answer {
+                    value: value.to_string(),
+                    kind: stringify!($t),
+                }
+            }
+        })*
+    };
+}
+
+impl_answer_from!(u32, u64, u128, usize, i64, BigUint);
+
+// What whoever runs a part hands down to it. Params let an example override
+// something the puzzle otherwise fixes, like day 11's expansion factor.
+pub struct Context<'a> {
+    pub progress: &'a dyn Progress,
+    pub cancel: &'a Cancel,
+    pub params: &'a BTreeMap<String, u64>,
+}
+
+impl Context<'_> {
+    pub fn param(&self, name: &str) -> Option<u64> {
+        self.params.get(name).copied()
+    }
+}
+
+static NEVER: Cancel = Cancel::never();
+static NO_PARAMS: BTreeMap<String, u64> = BTreeMap::new();
+
+impl Default for Context<'static> {
+    fn default() -> Self {
+        Context {
+            progress: &Silent,
+            cancel: &NEVER,
+            params: &NO_PARAMS,
+        }
+    }
+}
+
+// A day's puzzle input, parsed once and then solved for each part. A part gives
+// back None when it stopped early because `ctx.cancel` was set.
+pub trait Solution: Send + Sync {
+    fn parse(input: &str) -> Self
+    where
+        Self: Sized;
+
+    fn part_1(&self, ctx: &Context) -> Option<Answer>;
+
+    fn part_2(&self, ctx: &Context) -> Option<Answer>;
+}
+
+pub type Parse = fn(&str) -> Box<dyn Solution>;
+
+fn boxed<S: Solution + 'static>(input: &str) -> Box<dyn Solution> {
+    Box::new(S::parse(input))
+}
+
+pub const DAYS: [(u8, Parse); 11] = [
+    (1, boxed::<day1::Day1>),
+    (2, boxed::<day2::Day2>),
+    (3, boxed::<day3::Day3>),
+    (4, boxed::<day4::Day4>),
+    (5, boxed::<day5::Day5>),
+    (6, boxed::<day6::Day6>),
+    (7, boxed::<day7::Day7>),
+    (8, boxed::<day8::Day8>),
+    (9, boxed::<day9::Day9>),
+    (10, boxed::<day10::Day10>),
+    (11, boxed::<day11::Day11>),
+];
+
+pub fn solution(day: u8) -> Option<Parse> {
+    DAYS.iter()
+        .find(|(d, _)| *d == day)
+        .map(|&(_, parse)| parse)
+}
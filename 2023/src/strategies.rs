@@ -0,0 +1,223 @@
+use crate::shapes::{
+    almanac_text, card_line, draw_loop, game_line, histogram_outline, with_digit, COLORS,
+    DIGIT_WORDS,
+};
+use proptest::prelude::*;
+use proptest::sample::Index;
+
+// Day 1: a calibration line with at least one numeric digit.
+pub fn calibration_line() -> impl Strategy<Value = String> {
+    let token = prop_oneof![
+        prop::sample::select(&DIGIT_WORDS[..]).prop_map(String::from),
+        (0..10u32).prop_map(|d| d.to_string()),
+        "[a-z]{1,4}",
+    ];
+
+    (prop::collection::vec(token, 0..8), 0..10u32, any::<Index>()).prop_map(
+        |(tokens, digit, at)| {
+            let at = at.index(tokens.len() + 1);
+            with_digit(tokens, at, digit)
+        },
+    )
+}
+
+// Day 2: games numbered from 1 with up to five rounds of red, green and blue picks.
+pub fn games() -> impl Strategy<Value = String> {
+    let pick = (1..=20u32, 0..3usize);
+    let round = prop::collection::vec(pick, 1..=3).prop_map(|picks| {
+        let mut seen = [false; 3];
+        let mut out = Vec::new();
+        for (num, color) in picks {
+            if !std::mem::replace(&mut seen[color], true) {
+                out.push((num, COLORS[color]));
+            }
+        }
+        out
+    });
+    let game = prop::collection::vec(round, 1..=5);
+
+    prop::collection::vec(game, 1..20).prop_map(|games| {
+        games
+            .iter()
+            .enumerate()
+            .map(|(i, rounds)| game_line(i + 1, rounds))
+            .collect()
+    })
+}
+
+// Day 3: a possibly ragged schematic of short numbers and symbols.
+pub fn schematic() -> impl Strategy<Value = String> {
+    let cell = prop::sample::select(vec![
+        '.', '.', '.', '.', '0', '1', '4', '7', '9', '*', '*', '#', '$',
+    ]);
+    let line = prop::collection::vec(cell, 1..=6).prop_map(|cs| cs.into_iter().collect::<String>());
+
+    prop::collection::vec(line, 1..=8).prop_map(|lines| lines.join("\n") + "\n")
+}
+
+// Day 4: scratchcards that never win copies of cards past the end of the table.
+pub fn scratchcards() -> impl Strategy<Value = String> {
+    let pool = Just((1..100u32).collect::<Vec<_>>()).prop_shuffle();
+    let card = (pool, 1..=10usize, 0..=10usize, 0..=15usize);
+
+    prop::collection::vec(card, 1..30).prop_map(|cards| {
+        let n = cards.len();
+        let mut out = String::new();
+
+        for (i, (pool, winning, matches, misses)) in cards.into_iter().enumerate() {
+            let matches = matches.min(winning).min(n - 1 - i);
+            let misses = if matches == 0 { misses.max(1) } else { misses };
+            let have: Vec<u32> = pool[..matches]
+                .iter()
+                .chain(&pool[winning..winning + misses])
+                .copied()
+                .collect();
+
+            out.push_str(&card_line(i + 1, &pool[..winning], &have));
+        }
+
+        out
+    })
+}
+
+// Day 5: seed ranges followed by the seven category maps, each with disjoint
+// source ranges.
+pub fn almanac() -> impl Strategy<Value = String> {
+    let seeds = prop::collection::vec((0..100u64, 1..20u64), 1..=3);
+    let mapping = (0..20u64, 1..30u64, 0..200u64);
+    let range_map = prop::collection::vec(mapping, 1..=4);
+
+    (seeds, prop::collection::vec(range_map, 7)).prop_map(|(seeds, maps)| {
+        let maps: Vec<Vec<(u64, u64, u64)>> = maps
+            .iter()
+            .map(|mappings| {
+                let mut src = 0;
+                mappings
+                    .iter()
+                    .map(|(gap, len, dest)| {
+                        src += gap + len;
+                        (*dest, src - len, *len)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        almanac_text(&seeds, &maps)
+    })
+}
+
+// Day 6: (time, record distance) pairs.
+pub fn races() -> impl Strategy<Value = Vec<(u64, u64)>> {
+    let race = (1..100u64).prop_flat_map(|time| (Just(time), 0..=time * time / 4));
+
+    prop::collection::vec(race, 1..=4)
+}
+
+// Day 7: camel card hands with bets.
+pub fn hands() -> impl Strategy<Value = String> {
+    let hand = ("[2-9TJQKA]{5}", 1..1000u32);
+
+    prop::collection::vec(hand, 1..50).prop_map(|hands| {
+        hands
+            .iter()
+            .map(|(cards, bet)| format!("{cards} {bet}\n"))
+            .collect()
+    })
+}
+
+// Day 8: a network where `AAA` and every other `..A` node head down their own chain to
+// a `..Z` node, which loops back to the second node of that chain. Each ghost
+// therefore reaches `Z` exactly at multiples of its chain length.
+pub fn network() -> impl Strategy<Value = String> {
+    let path = "[LR]{1,6}";
+    let chains = prop::collection::vec(2..=8usize, 1..=4);
+
+    (path, chains).prop_map(|(path, chains)| {
+        let mut out = format!("{path}\n\n");
+
+        for (ghost, &len) in chains.iter().enumerate() {
+            let name = |i: usize| match (ghost, i) {
+                (0, 0) => "AAA".to_string(),
+                (0, i) if i == len => "ZZZ".to_string(),
+                (0, i) => format!("AA{i}"),
+                (g, 0) => format!("{g}0A"),
+                (g, i) if i == len => format!("{g}9Z"),
+                (g, i) => format!("{g}{i}X"),
+            };
+
+            for i in 0..=len {
+                let next = if i == len { name(1) } else { name(i + 1) };
+                out.push_str(&format!("{} = ({next}, {next})\n", name(i)));
+            }
+        }
+
+        out
+    })
+}
+
+// Day 9: sequences sampled from integer polynomials, with the sums of the true next
+// and previous values.
+pub fn polynomial_sequences() -> impl Strategy<Value = (String, i64, i64)> {
+    let coeffs = prop::collection::vec(-5..=5i64, 1..=4);
+    let seq = (coeffs, 0..6usize);
+
+    prop::collection::vec(seq, 1..10).prop_map(|seqs| {
+        let mut out = String::new();
+        let mut next = 0;
+        let mut prev = 0;
+
+        for (coeffs, extra) in seqs {
+            let len = coeffs.len() + 1 + extra;
+            let eval = |x: i64| coeffs.iter().rev().fold(0, |acc, c| acc * x + c);
+
+            let nums: Vec<String> = (0..len as i64).map(|x| eval(x).to_string()).collect();
+            out.push_str(&nums.join(" "));
+            out.push('\n');
+
+            next += eval(len as i64);
+            prev += eval(-1);
+        }
+
+        (out, next, prev)
+    })
+}
+
+// Day 10: the outline of a histogram of random column heights, traced at double
+// scale so no two stretches of pipe touch, with `S` somewhere on it and stray pipe
+// pieces scattered around away from `S`.
+pub fn pipe_loop() -> impl Strategy<Value = String> {
+    let heights = prop::collection::vec(1..=4usize, 1..=6);
+    let junk = prop::sample::select(vec!['.', '.', '|', '-', 'L', 'J', '7', 'F']);
+
+    (
+        heights,
+        any::<Index>(),
+        prop::collection::vec(junk, 11 * 15),
+    )
+        .prop_map(|(heights, start, junk)| {
+            let path = histogram_outline(&heights, 4);
+            let (width, height) = (2 * heights.len() + 3, 11);
+
+            let mut grid: Vec<Vec<char>> = junk
+                .chunks(15)
+                .take(height)
+                .map(|row| row[..width].to_vec())
+                .collect();
+            draw_loop(&mut grid, &path, start.index(path.len()));
+
+            grid.iter()
+                .map(|row| row.iter().collect::<String>() + "\n")
+                .collect()
+        })
+}
+
+// Day 11: a rectangular image with roughly one galaxy in five cells.
+pub fn galaxy_map() -> impl Strategy<Value = String> {
+    (1..15usize, 1..15usize).prop_flat_map(|(rows, cols)| {
+        let cell = prop::sample::select(vec!['.', '.', '.', '.', '#']);
+        let line =
+            prop::collection::vec(cell, cols).prop_map(|cs| cs.into_iter().collect::<String>());
+
+        prop::collection::vec(line, rows).prop_map(|lines| lines.join("\n") + "\n")
+    })
+}
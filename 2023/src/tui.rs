@@ -0,0 +1,203 @@
+use crate::visualize::{Cell, Frame, Shade, Visualize};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+use ratatui::DefaultTerminal;
+use std::io;
+use std::time::{Duration, Instant};
+
+const KEYS: &str = "space play/pause  ←/→ step  home/end  +/- speed  q quit";
+
+// Where the viewer is in the frames, apart from the terminal it draws on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Player {
+    pub idx: usize,
+    pub count: usize,
+    pub playing: bool,
+    pub fps: u32,
+}
+
+impl Player {
+    pub fn new(count: usize, fps: u32) -> Self {
+        Player {
+            idx: 0,
+            count,
+            playing: false,
+            fps: fps.clamp(1, 120),
+        }
+    }
+
+    // Applies a key press, returning false once the viewer should close.
+    pub fn press(&mut self, key: KeyCode) -> bool {
+        let last = self.count.saturating_sub(1);
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char(' ') => {
+                // Playing from the end starts over.
+                if !self.playing && self.idx == last {
+                    self.idx = 0;
+                }
+                self.playing = !self.playing;
+            }
+            KeyCode::Right | KeyCode::Char('l') => {
+                self.playing = false;
+                self.idx = (self.idx + 1).min(last);
+            }
+            KeyCode::Left | KeyCode::Char('h') => {
+                self.playing = false;
+                self.idx = self.idx.saturating_sub(1);
+            }
+            KeyCode::Home | KeyCode::Char('g') => self.idx = 0,
+            KeyCode::End | KeyCode::Char('G') => self.idx = last,
+            KeyCode::Char('+') | KeyCode::Char('=') => self.fps = (self.fps * 2).min(120),
+            KeyCode::Char('-') => self.fps = (self.fps / 2).max(1),
+            _ => {}
+        }
+        true
+    }
+
+    // Moves on a frame while playing, pausing on the last one.
+    pub fn tick(&mut self) {
+        if !self.playing {
+            return;
+        }
+        if self.idx + 1 < self.count {
+            self.idx += 1;
+        }
+        if self.idx + 1 >= self.count {
+            self.playing = false;
+        }
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs(1) / self.fps
+    }
+}
+
+// Shows the frames full screen until the viewer is closed.
+pub fn run(viz: &dyn Visualize, fps: u32) -> io::Result<()> {
+    let mut terminal = ratatui::init();
+    let result = play(&mut terminal, viz, Player::new(viz.frame_count(), fps));
+    ratatui::restore();
+    result
+}
+
+fn play(terminal: &mut DefaultTerminal, viz: &dyn Visualize, mut player: Player) -> io::Result<()> {
+    let mut frame = viz.frame(player.idx);
+    let mut shown = player.idx;
+    let mut next_tick = Instant::now() + player.interval();
+
+    loop {
+        if player.idx != shown {
+            frame = viz.frame(player.idx);
+            shown = player.idx;
+        }
+        terminal.draw(|f| draw(f, &frame, &player))?;
+
+        let timeout = if player.playing {
+            next_tick.saturating_duration_since(Instant::now())
+        } else {
+            Duration::from_secs(60)
+        };
+        if event::poll(timeout)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press && !player.press(key.code) {
+                    return Ok(());
+                }
+            }
+        }
+
+        let now = Instant::now();
+        if now >= next_tick {
+            player.tick();
+            next_tick = now + player.interval();
+        }
+    }
+}
+
+fn draw(f: &mut ratatui::Frame, frame: &Frame, player: &Player) {
+    let [grid, status] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(2)]).areas(f.area());
+
+    let lines: Vec<Line> = frame.rows.iter().map(|row| line(row)).collect();
+    f.render_widget(Paragraph::new(lines).scroll(scroll(frame, grid)), grid);
+
+    let state = if player.playing { "playing" } else { "paused" };
+    let status_lines = vec![
+        Line::from(format!(
+            "{}/{} {state} at {} fps  {}",
+            player.idx + 1,
+            player.count,
+            player.fps,
+            frame.caption
+        )),
+        Line::styled(KEYS, Style::new().fg(Color::DarkGray)),
+    ];
+    f.render_widget(Paragraph::new(status_lines), status);
+}
+
+// A row as spans of cells that share a shade.
+fn line(row: &[Cell]) -> Line<'static> {
+    let mut spans = Vec::new();
+    let mut cells = row.iter().peekable();
+    while let Some(first) = cells.next() {
+        let mut text = first.glyph.to_string();
+        while let Some(cell) = cells.next_if(|cell| cell.shade == first.shade) {
+            text.push(cell.glyph);
+        }
+        spans.push(Span::styled(text, style(first.shade)));
+    }
+    Line::from(spans)
+}
+
+fn style(shade: Shade) -> Style {
+    match shade {
+        Shade::Dim => Style::new().fg(Color::DarkGray),
+        Shade::Plain => Style::new(),
+        Shade::Marked => Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        Shade::Active => Style::new().fg(Color::Black).bg(Color::Yellow),
+        Shade::Filled => Style::new().fg(Color::Black).bg(Color::Green),
+    }
+}
+
+// Keeps the frame's focus in the middle of the area when it doesn't all fit.
+fn scroll(frame: &Frame, area: Rect) -> (u16, u16) {
+    let (x, y) = frame.focus().unwrap_or((0, 0));
+    let centre = |at: usize, size: usize, view: u16| {
+        let view = usize::from(view);
+        at.saturating_sub(view / 2).min(size.saturating_sub(view)) as u16
+    };
+    (
+        centre(y, frame.rows.len(), area.height),
+        centre(x, frame.width(), area.width),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tui::Player;
+    use ratatui::crossterm::event::KeyCode;
+
+    #[test]
+    fn test_player() {
+        let mut player = Player::new(3, 10);
+
+        player.press(KeyCode::Right);
+        player.press(KeyCode::Right);
+        player.press(KeyCode::Right);
+        assert_eq!(2, player.idx);
+
+        // Playing from the last frame starts from the first and stops at the end.
+        player.press(KeyCode::Char(' '));
+        assert_eq!((0, true), (player.idx, player.playing));
+        player.tick();
+        player.tick();
+        assert_eq!((2, false), (player.idx, player.playing));
+
+        player.press(KeyCode::Char('-'));
+        assert_eq!(5, player.fps);
+        assert!(!player.press(KeyCode::Char('q')));
+    }
+}
@@ -0,0 +1,121 @@
+use crate::{day10, day11, day3, day8};
+
+// What a cell stands for in a frame, leaving the colours to whatever draws it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Shade {
+    Dim,
+    Plain,
+    Marked,
+    Active,
+    Filled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub glyph: char,
+    pub shade: Shade,
+}
+
+// One step of a visualization: a grid of cells, row by row, and a line saying
+// what's going on in it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Frame {
+    pub caption: String,
+    pub rows: Vec<Vec<Cell>>,
+}
+
+impl Frame {
+    pub fn new(caption: impl Into<String>) -> Self {
+        Frame {
+            caption: caption.into(),
+            rows: Vec::new(),
+        }
+    }
+
+    // Appends `text` to the last row, or to a new one when there isn't any yet.
+    pub fn push_text(&mut self, text: &str, shade: Shade) {
+        if self.rows.is_empty() {
+            self.rows.push(Vec::new());
+        }
+        let row = self.rows.last_mut().unwrap();
+        row.extend(text.chars().map(|glyph| Cell { glyph, shade }));
+    }
+
+    pub fn new_row(&mut self) {
+        self.rows.push(Vec::new());
+    }
+
+    pub fn width(&self) -> usize {
+        self.rows.iter().map(Vec::len).max().unwrap_or(0)
+    }
+
+    // Where the first `Active` cell is, as (column, row), for keeping it in view.
+    pub fn focus(&self) -> Option<(usize, usize)> {
+        self.rows.iter().enumerate().find_map(|(y, row)| {
+            row.iter()
+                .position(|cell| cell.shade == Shade::Active)
+                .map(|x| (x, y))
+        })
+    }
+
+    // The glyphs alone, a line per row.
+    pub fn text(&self) -> String {
+        self.rows
+            .iter()
+            .map(|row| row.iter().map(|cell| cell.glyph).collect::<String>() + "\n")
+            .collect()
+    }
+}
+
+// A day's solution told as a sequence of frames. Frames are made on demand, so
+// that long ones such as a loop traversal don't have to be held all at once.
+pub trait Visualize {
+    fn frame_count(&self) -> usize;
+    fn frame(&self, idx: usize) -> Frame;
+}
+
+// The day's visualization of `input`, None if it doesn't have one, or why it
+// can't be shown for this input.
+pub fn visualizer(day: u8, input: &str) -> Result<Option<Box<dyn Visualize>>, String> {
+    Ok(Some(match day {
+        3 => Box::new(day3::PartScan::new(input).map_err(|err| err.to_string())?),
+        8 => Box::new(day8::GhostWalk::new(input)?),
+        10 => Box::new(day10::LoopWalk::new(input).map_err(|err| err.to_string())?),
+        11 => Box::new(day11::Expansion::new(input)),
+        _ => return Ok(None),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::visualize::{visualizer, Frame, Shade};
+
+    #[test]
+    fn test_frame() {
+        let mut frame = Frame::new("caption");
+        frame.push_text("ab", Shade::Plain);
+        frame.new_row();
+        frame.push_text("c", Shade::Plain);
+        frame.push_text("d", Shade::Active);
+
+        assert_eq!("ab\ncd\n", frame.text());
+        assert_eq!(2, frame.width());
+        assert_eq!(Some((1, 1)), frame.focus());
+    }
+
+    #[test]
+    fn test_visualizer() {
+        assert!(visualizer(1, "").unwrap().is_none());
+
+        let viz = visualizer(10, ".....\n.S-7.\n.|.|.\n.L-J.\n.....\n")
+            .unwrap()
+            .unwrap();
+        assert_eq!(9, viz.frame_count());
+
+        // A grid without a loop can't be walked.
+        assert_eq!(
+            Some("the pipe from S doesn't loop back to it".to_string()),
+            visualizer(10, ".....\n.S-7.\n.|...\n.L-J.\n.....\n").err()
+        );
+    }
+}
@@ -0,0 +1,28 @@
+// Counting is only switched on, never off, so this runs in a test binary of its own
+// rather than alongside the runner's tests, whose stats it would change.
+use aoc2023::alloc::{enable, measure, AllocStats, Counting};
+use std::alloc::{GlobalAlloc, Layout};
+
+// Counting isn't the test binary's allocator, so only the calls made here count.
+#[test]
+fn test_measure() {
+    enable();
+    let layout = Layout::from_size_align(1024, 8).unwrap();
+
+    let (_, stats) = measure(|| unsafe {
+        let a = Counting.alloc(layout);
+        let b = Counting.alloc_zeroed(layout);
+        Counting.dealloc(a, layout);
+        let b = Counting.realloc(b, layout, 4096);
+        Counting.dealloc(b, Layout::from_size_align(4096, 8).unwrap());
+    });
+
+    assert_eq!(
+        Some(AllocStats {
+            allocs: 3,
+            bytes: 1024 + 1024 + 4096,
+            peak_bytes: 4096,
+        }),
+        stats
+    );
+}